@@ -4,8 +4,8 @@ mod events;
 use events::{EventCategory, EventPriority, RemitwiseEvents};
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
+    Env, Map, String, Symbol, Vec,
 };
 
 // If upstream added a schedule module, we keep the declaration but don't use it if it's causing errors.
@@ -35,6 +35,12 @@ pub struct Bill {
     pub paid_at: Option<u64>,
     // Merged from upstream: Keep this to match their data shape
     pub schedule_id: Option<u32>,
+    /// Extra amount owed when paid after `due_date`
+    pub late_fee: i128,
+    /// Maximum number of bills in a recurring chain (0 = unlimited)
+    pub max_occurrences: u32,
+    /// Position of this bill in its recurring chain, starting at 1
+    pub occurrence: u32,
 }
 
 /// Function names for selective pause (symbol_short max 9 chars)
@@ -45,10 +51,14 @@ pub mod pause_functions {
     pub const CANCEL_BILL: soroban_sdk::Symbol = symbol_short!("can_bill");
     pub const ARCHIVE: soroban_sdk::Symbol = symbol_short!("archive");
     pub const RESTORE: soroban_sdk::Symbol = symbol_short!("restore");
+    pub const TRANSFER: soroban_sdk::Symbol = symbol_short!("transfer");
 }
 
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+const SNAPSHOT_VERSION: u32 = 1;
+/// Highest bill id `import_bills` accepts, so imports can never exhaust the id space
+const MAX_IMPORTED_BILL_ID: u32 = u32::MAX / 2;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -64,6 +74,22 @@ pub enum Error {
     FunctionPaused = 8,
     BatchTooLarge = 9,
     BatchValidationFailed = 10,
+    InvalidNonce = 11,
+    UnsupportedSnapshotVersion = 12,
+    SnapshotChecksumMismatch = 13,
+    BillNotRecurring = 14,
+    TreasuryNotSet = 15,
+    InvalidBillId = 16,
+}
+
+/// One owner's bills, exported for backup/migration. Checksum is numeric for on-chain verification.
+#[contracttype]
+#[derive(Clone)]
+pub struct BillsExportSnapshot {
+    pub version: u32,
+    pub checksum: u64,
+    pub next_id: u32,
+    pub bills: Vec<Bill>,
 }
 
 /// Archived bill
@@ -316,7 +342,9 @@ impl BillPayments {
         Ok(())
     }
 
-    /// Create a new bill
+    /// Create a new bill. `late_fee` (0 for none) is added to the amount owed once
+    /// the bill is paid after `due_date`; recurring successors inherit it.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_bill(
         env: Env,
         owner: Address,
@@ -325,11 +353,12 @@ impl BillPayments {
         due_date: u64,
         recurring: bool,
         frequency_days: u32,
+        late_fee: i128,
     ) -> Result<u32, Error> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
 
-        if amount <= 0 {
+        if amount <= 0 || late_fee < 0 {
             return Err(Error::InvalidAmount);
         }
 
@@ -364,6 +393,9 @@ impl BillPayments {
             created_at: current_time,
             paid_at: None,
             schedule_id: None, // Initialize to None
+            late_fee,
+            max_occurrences: 0,
+            occurrence: 1,
         };
 
         let bill_owner = bill.owner.clone();
@@ -391,17 +423,66 @@ impl BillPayments {
     pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::mark_paid(&env, &caller, bill_id, false)?;
+        Ok(())
+    }
 
-        Self::extend_instance_ttl(&env);
+    /// Pay a bill in tokens: transfers the amount owed (late fee included) from
+    /// `caller` to the treasury and marks the bill paid.
+    ///
+    /// # Returns
+    /// The amount transferred
+    pub fn pay_bill_usdc(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        usdc_contract: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let treasury = Self::get_treasury(env.clone()).ok_or(Error::TreasuryNotSet)?;
+
+        let paid_amount = Self::mark_paid(&env, &caller, bill_id, true)?;
+        TokenClient::new(&env, &usdc_contract).transfer(&caller, &treasury, &paid_amount);
+        Ok(paid_amount)
+    }
+
+    /// Set the address `pay_bill_usdc` sends payments to (admin only).
+    pub fn set_treasury(env: Env, caller: Address, treasury: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(Error::UnauthorizedPause)?;
+        if admin != caller {
+            return Err(Error::UnauthorizedPause);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("TREASURY"), &treasury);
+        Ok(())
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("TREASURY"))
+    }
+
+    /// Shared body of `pay_bill` and `pay_bill_usdc`; returns the amount paid. Only
+    /// token payments (`with_late_fee`) collect the late fee; bookkeeping-only
+    /// payments record the bill amount.
+    fn mark_paid(
+        env: &Env,
+        caller: &Address,
+        bill_id: u32,
+        with_late_fee: bool,
+    ) -> Result<i128, Error> {
+        Self::extend_instance_ttl(env);
         let mut bills: Map<u32, Bill> = env
             .storage()
             .instance()
             .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
 
         let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
 
-        if bill.owner != caller {
+        if bill.owner != *caller {
             return Err(Error::Unauthorized);
         }
 
@@ -410,11 +491,16 @@ impl BillPayments {
         }
 
         let current_time = env.ledger().timestamp();
+        let paid_amount = if with_late_fee {
+            Self::amount_owed(&bill, current_time)
+        } else {
+            bill.amount
+        };
         bill.paid = true;
         bill.paid_at = Some(current_time);
 
         // Handle recurring logic
-        if bill.recurring {
+        if Self::spawns_successor(&bill) {
             let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
             let next_id = env
                 .storage()
@@ -435,6 +521,9 @@ impl BillPayments {
                 created_at: current_time,
                 paid_at: None,
                 schedule_id: bill.schedule_id, // Preserve schedule ID
+                late_fee: bill.late_fee,
+                max_occurrences: bill.max_occurrences,
+                occurrence: bill.occurrence.saturating_add(1),
             };
             bills.set(next_id, next_bill);
             env.storage()
@@ -442,7 +531,6 @@ impl BillPayments {
                 .set(&symbol_short!("NEXT_ID"), &next_id);
         }
 
-        let paid_amount = bill.amount;
         bills.set(bill_id, bill);
         env.storage()
             .instance()
@@ -450,16 +538,156 @@ impl BillPayments {
 
         // Standardized Notification
         RemitwiseEvents::emit(
-            &env,
+            env,
             EventCategory::Transaction,
             EventPriority::High,
             symbol_short!("paid"),
-            (bill_id, caller, paid_amount),
+            (bill_id, caller.clone(), paid_amount),
         );
 
+        Ok(paid_amount)
+    }
+
+    /// Cap the length of a recurring bill's chain. Once the bill at position
+    /// `max_occurrences` is paid, no further successor is created. 0 removes the cap.
+    pub fn set_max_occurrences(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        max_occurrences: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.max_occurrences = max_occurrences;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("max_occ"),
+            (bill_id, max_occurrences),
+        );
         Ok(())
     }
 
+    /// Change how often a recurring bill repeats. Successors created when this bill
+    /// is paid are due `new_frequency_days` after it.
+    pub fn reschedule_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        new_frequency_days: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+
+        if new_frequency_days == 0 {
+            return Err(Error::InvalidFrequency);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if !bill.recurring {
+            return Err(Error::BillNotRecurring);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.frequency_days = new_frequency_days;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("updated"),
+            (bill_id, new_frequency_days),
+        );
+        Ok(())
+    }
+
+    /// Position of a bill in its recurring chain (1 for the first bill, 0 if not found)
+    pub fn get_occurrence_count(env: Env, bill_id: u32) -> u32 {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        bills.get(bill_id).map(|bill| bill.occurrence).unwrap_or(0)
+    }
+
+    fn spawns_successor(bill: &Bill) -> bool {
+        bill.recurring && (bill.max_occurrences == 0 || bill.occurrence < bill.max_occurrences)
+    }
+
+    /// Total owed on a bill at `now_ts`: the amount, plus the late fee once past due.
+    pub fn get_amount_owed(env: Env, bill_id: u32, now_ts: u64) -> Result<i128, Error> {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        Ok(Self::amount_owed(&bill, now_ts))
+    }
+
+    fn amount_owed(bill: &Bill, now_ts: u64) -> i128 {
+        if now_ts > bill.due_date {
+            bill.amount.saturating_add(bill.late_fee)
+        } else {
+            bill.amount
+        }
+    }
+
+    /// `owner`'s unpaid bills as `(bill_id, amount owed at now_ts)`, late fees included
+    pub fn get_amounts_owed(env: Env, owner: Address, now_ts: u64) -> Vec<(u32, i128)> {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for (id, bill) in bills.iter() {
+            if !bill.paid && bill.owner == owner {
+                result.push_back((id, Self::amount_owed(&bill, now_ts)));
+            }
+        }
+        result
+    }
+
     pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
         let bills: Map<u32, Bill> = env
             .storage()
@@ -469,6 +697,16 @@ impl BillPayments {
         bills.get(bill_id)
     }
 
+    /// ID the next created bill will receive. Bills created concurrently, including
+    /// recurring successors spawned by payments, can claim it first.
+    pub fn peek_next_bill_id(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+            + 1
+    }
+
     pub fn get_unpaid_bills(env: Env, owner: Address) -> Vec<Bill> {
         let bills: Map<u32, Bill> = env
             .storage()
@@ -500,6 +738,34 @@ impl BillPayments {
         result
     }
 
+    /// True if `owner` has any unpaid bill due before `now_ts`; stops at the first match.
+    pub fn has_overdue_bills(env: Env, owner: Address, now_ts: u64) -> bool {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        bills
+            .iter()
+            .any(|(_, bill)| !bill.paid && bill.due_date < now_ts && bill.owner == owner)
+    }
+
+    /// Number of `owner`'s unpaid bills due before `now_ts`.
+    pub fn overdue_count(env: Env, owner: Address, now_ts: u64) -> u32 {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut count = 0u32;
+        for (_, bill) in bills.iter() {
+            if !bill.paid && bill.due_date < now_ts && bill.owner == owner {
+                count += 1;
+            }
+        }
+        count
+    }
+
     pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
         let bills: Map<u32, Bill> = env
             .storage()
@@ -542,6 +808,51 @@ impl BillPayments {
         Ok(())
     }
 
+    /// Hand an unpaid bill over to `new_owner`, who must also authorize taking it on.
+    /// Recurring successors created when the bill is later paid inherit the new owner.
+    pub fn transfer_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        new_owner: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if new_owner != caller {
+            new_owner.require_auth();
+        }
+        Self::require_not_paused(&env, pause_functions::TRANSFER)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.owner = new_owner.clone();
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("transfer"),
+            (bill_id, caller, new_owner),
+        );
+        Ok(())
+    }
+
     pub fn archive_paid_bills(
         env: Env,
         caller: Address,
@@ -666,6 +977,9 @@ impl BillPayments {
             created_at: archived_bill.paid_at,
             paid_at: Some(archived_bill.paid_at),
             schedule_id: None, // Reset schedule on restore
+            late_fee: 0,
+            max_occurrences: 0,
+            occurrence: 1,
         };
 
         bills.set(bill_id, restored_bill);
@@ -775,7 +1089,7 @@ impl BillPayments {
             let amount = bill.amount;
             bill.paid = true;
             bill.paid_at = Some(current_time);
-            if bill.recurring {
+            if Self::spawns_successor(&bill) {
                 next_id = next_id.saturating_add(1);
                 let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
                 let next_bill = Bill {
@@ -790,6 +1104,9 @@ impl BillPayments {
                     created_at: current_time,
                     paid_at: None,
                     schedule_id: bill.schedule_id,
+                    late_fee: bill.late_fee,
+                    max_occurrences: bill.max_occurrences,
+                    occurrence: bill.occurrence.saturating_add(1),
                 };
                 bills.set(next_id, next_bill);
             }
@@ -840,6 +1157,38 @@ impl BillPayments {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Extend instance storage TTL so bills survive long read-only periods. No auth required.
+    pub fn bump_ttl(env: Env) -> bool {
+        Self::extend_instance_ttl(&env);
+        true
+    }
+
+    /// Keeper variant of `bump_ttl`: extends the TTL of the instance storage that holds
+    /// the `BILLS` map and checks which of `ids` (at most `MAX_BATCH_SIZE`) it still
+    /// holds. No auth required; bill contents are not changed.
+    ///
+    /// # Returns
+    /// How many of `ids` are existing bills
+    pub fn bump_many(env: Env, ids: Vec<u32>) -> Result<u32, Error> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut found = 0u32;
+        for id in ids.iter() {
+            if bills.contains_key(id) {
+                found += 1;
+            }
+        }
+        Ok(found)
+    }
+
     fn extend_archive_ttl(env: &Env) {
         env.storage()
             .instance()
@@ -928,6 +1277,154 @@ impl BillPayments {
         }
         Ok(result)
     }
+
+    // --- Snapshot export/import ---
+
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address))
+            .unwrap_or(0)
+    }
+
+    /// Export `owner`'s bills only; other owners' bills are never included.
+    pub fn export_bills(env: Env, owner: Address) -> BillsExportSnapshot {
+        owner.require_auth();
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut list = Vec::new(&env);
+        for (_, bill) in bills.iter() {
+            if bill.owner == owner {
+                list.push_back(bill);
+            }
+        }
+        let checksum = Self::compute_bills_checksum(SNAPSHOT_VERSION, next_id, &list);
+        BillsExportSnapshot {
+            version: SNAPSHOT_VERSION,
+            checksum,
+            next_id,
+            bills: list,
+        }
+    }
+
+    /// Merge an exported snapshot into this contract. Every bill must belong to `caller`,
+    /// and an existing bill with the same id may only be replaced if `caller` owns it.
+    /// Imported ids must lie in `1..=MAX_IMPORTED_BILL_ID`. `NEXT_ID` advances to the
+    /// highest imported id so new bills never reuse one; the snapshot's `next_id` is
+    /// only covered by the checksum.
+    pub fn import_bills(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        snapshot: BillsExportSnapshot,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(Error::UnsupportedSnapshotVersion);
+        }
+        let expected =
+            Self::compute_bills_checksum(snapshot.version, snapshot.next_id, &snapshot.bills);
+        if snapshot.checksum != expected {
+            return Err(Error::SnapshotChecksumMismatch);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        for bill in snapshot.bills.iter() {
+            if bill.owner != caller {
+                return Err(Error::Unauthorized);
+            }
+            if bill.id == 0 || bill.id > MAX_IMPORTED_BILL_ID {
+                return Err(Error::InvalidBillId);
+            }
+            if let Some(existing) = bills.get(bill.id) {
+                if existing.owner != caller {
+                    return Err(Error::Unauthorized);
+                }
+            }
+        }
+
+        let mut next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut imported = 0u32;
+        for bill in snapshot.bills.iter() {
+            next_id = next_id.max(bill.id);
+            bills.set(bill.id, bill);
+            imported += 1;
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+
+        Self::increment_nonce(&env, &caller);
+        Self::update_storage_stats(&env);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("imported"),
+            (caller, imported),
+        );
+        Ok(imported)
+    }
+
+    fn require_nonce(env: &Env, address: &Address, expected: u64) -> Result<(), Error> {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        if expected != current {
+            return Err(Error::InvalidNonce);
+        }
+        Ok(())
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        let next = current.checked_add(1).expect("nonce overflow");
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(env));
+        nonces.set(address.clone(), next);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    }
+
+    fn compute_bills_checksum(version: u32, next_id: u32, bills: &Vec<Bill>) -> u64 {
+        let mut c = version as u64 + next_id as u64;
+        for bill in bills.iter() {
+            c = c
+                .wrapping_add(bill.id as u64)
+                .wrapping_add(bill.amount as u64)
+                .wrapping_add(bill.due_date)
+                .wrapping_add(bill.paid as u64);
+        }
+        c.wrapping_mul(31)
+    }
 }
 
 // Ensure tests module is linked
@@ -35,6 +35,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         assert_eq!(bill_id, 1);
@@ -61,6 +62,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         assert_eq!(result, Err(Ok(Error::InvalidAmount)));
@@ -81,6 +83,7 @@ mod testsuit {
             &1000000,
             &true,
             &0,
+            &0,
         );
 
         assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
@@ -101,6 +104,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         assert_eq!(result, Err(Ok(Error::InvalidAmount)));
@@ -121,6 +125,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         env.mock_all_auths();
@@ -146,6 +151,7 @@ mod testsuit {
             &1000000,
             &true,
             &30,
+            &0,
         );
 
         env.mock_all_auths();
@@ -177,6 +183,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -186,6 +193,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -195,6 +203,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.pay_bill(&owner, &1);
@@ -217,6 +226,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -226,6 +236,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -235,6 +246,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.pay_bill(&owner, &1);
@@ -269,6 +281,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.pay_bill(&owner, &bill_id);
@@ -293,6 +306,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -302,6 +316,7 @@ mod testsuit {
             &1500000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.create_bill(
@@ -311,6 +326,7 @@ mod testsuit {
             &3000000,
             &false,
             &0,
+            &0,
         );
 
         let overdue = client.get_overdue_bills(&owner);
@@ -331,6 +347,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.cancel_bill(&owner, &bill_id);
@@ -352,6 +369,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         env.mock_all_auths();
         client.cancel_bill(&owner, &bill_id);
@@ -375,6 +393,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         let result = client.try_cancel_bill(&other, &bill_id);
@@ -407,6 +426,7 @@ mod testsuit {
             &1000000,
             &true,
             &30,
+            &0,
         );
         env.mock_all_auths();
         // Pay first bill - creates second
@@ -443,6 +463,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &owner,
@@ -451,6 +472,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &owner,
@@ -459,6 +481,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.pay_bill(&owner, &1);
 
@@ -482,6 +505,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         let result = client.try_pay_bill(&other, &bill_id);
@@ -503,6 +527,7 @@ mod testsuit {
             &1000000,
             &true, // Recurring
             &30,
+            &0,
         );
 
         // Cancel the bill
@@ -533,6 +558,7 @@ mod testsuit {
             &1000000, // Due in past
             &false,
             &0,
+            &0,
         );
 
         // Verify it shows up in overdue
@@ -562,6 +588,7 @@ mod testsuit {
             &1000000,
             &true, // Recurring
             &1,    // Daily
+            &0,
         );
 
         client.pay_bill(&owner, &bill_id);
@@ -585,6 +612,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &owner,
@@ -593,6 +621,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         let bills = client.get_all_bills_for_owner(&owner);
@@ -619,6 +648,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &alice,
@@ -627,6 +657,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &bob,
@@ -635,6 +666,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         let alice_bills = client.get_all_bills_for_owner(&alice);
@@ -668,6 +700,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         // Bob never created a bill
@@ -691,6 +724,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.pay_bill(&owner, &bill_id);
 
@@ -715,6 +749,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &owner,
@@ -723,6 +758,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
         client.cancel_bill(&owner, &bill_id);
 
@@ -750,6 +786,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         // Alice tries to call the admin-only endpoint
@@ -796,6 +833,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &86400);
@@ -826,6 +864,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &86400);
@@ -853,6 +892,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &86400);
@@ -879,6 +919,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &0);
@@ -910,6 +951,7 @@ mod testsuit {
             &2000,
             &true,
             &30,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &86400);
@@ -939,6 +981,7 @@ mod testsuit {
             &2000,
             &true,
             &30,
+            &0,
         );
 
         let schedule_id = client.create_schedule(&owner, &bill_id, &3000, &86400);
@@ -968,6 +1011,7 @@ mod testsuit {
             &6000,
             &false,
             &0,
+            &0,
         );
 
         let result = client.try_create_schedule(&owner, &bill_id, &3000, &86400);
@@ -991,6 +1035,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         let bill_id2 = client.create_bill(
@@ -1000,6 +1045,7 @@ mod testsuit {
             &2000,
             &false,
             &0,
+            &0,
         );
 
         client.create_schedule(&owner, &bill_id1, &3000, &86400);
@@ -1028,6 +1074,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         let events = env.events().all();
@@ -1070,6 +1117,7 @@ mod testsuit {
             &1000000,
             &false,
             &0,
+            &0,
         );
 
         env.mock_all_auths();
@@ -1116,6 +1164,7 @@ mod testsuit {
             &1_000_000,
             &false,
             &0,
+            &0,
         );
         client.create_bill(
             &alice,
@@ -1124,6 +1173,7 @@ mod testsuit {
             &1_500_000,
             &false,
             &0,
+            &0,
         );
 
         // Bob has 1 overdue bill
@@ -1134,6 +1184,7 @@ mod testsuit {
             &1_000_000,
             &false,
             &0,
+            &0,
         );
 
         // Alice has 1 future bill (not overdue)
@@ -1144,6 +1195,7 @@ mod testsuit {
             &3_000_000,
             &false,
             &0,
+            &0,
         );
 
         let alice_overdue = client.get_overdue_bills(&alice);
@@ -1159,4 +1211,487 @@ mod testsuit {
         assert_eq!(bob_overdue.len(), 1);
         assert_eq!(bob_overdue.get(0).unwrap().owner, bob);
     }
+
+    #[test]
+    fn test_bump_ttl_requires_no_auth_and_keeps_bills() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1000,
+            &1000000,
+            &false,
+            &0,
+            &0,
+        );
+
+        assert!(client.bump_ttl());
+        // No authorization was required for the bump
+        assert!(env.auths().is_empty());
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.amount, 1000);
+        assert!(!bill.paid);
+        assert_eq!(client.get_unpaid_bills(&owner).len(), 1);
+    }
+
+    #[test]
+    fn test_transfer_bill_switches_access_and_listing() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        let roommate = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Internet"),
+            &500,
+            &1000000,
+            &true,
+            &30,
+            &0,
+        );
+
+        client.transfer_bill(&owner, &bill_id, &roommate);
+        // Both the current and the new owner had to sign
+        let auths = env.auths();
+        assert!(auths.iter().any(|(addr, _)| *addr == owner));
+        assert!(auths.iter().any(|(addr, _)| *addr == roommate));
+        assert_eq!(client.get_bill(&bill_id).unwrap().owner, roommate);
+        assert_eq!(client.get_unpaid_bills(&owner).len(), 0);
+        assert_eq!(client.get_unpaid_bills(&roommate).len(), 1);
+
+        // The previous owner can no longer pay or transfer it
+        let result = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_transfer_bill(&owner, &bill_id, &owner);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        // The new owner pays and the recurring successor is theirs too
+        client.pay_bill(&roommate, &bill_id);
+        let unpaid = client.get_unpaid_bills(&roommate);
+        assert_eq!(unpaid.len(), 1);
+        assert_eq!(unpaid.get(0).unwrap().owner, roommate);
+        assert_eq!(client.get_unpaid_bills(&owner).len(), 0);
+    }
+
+    #[test]
+    fn test_late_fee_applies_only_after_due_date() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        set_time(&env, 500_000);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &1000,
+            &1_000_000,
+            &true,
+            &30,
+            &75,
+        );
+
+        // On time (including exactly at the due date): no fee
+        assert_eq!(client.get_amount_owed(&bill_id, &900_000), 1000);
+        assert_eq!(client.get_amount_owed(&bill_id, &1_000_000), 1000);
+        // Late: fee added
+        assert_eq!(client.get_amount_owed(&bill_id, &1_000_001), 1075);
+
+        let result = client.try_create_bill(
+            &owner,
+            &String::from_str(&env, "Gas"),
+            &1000,
+            &1_000_000,
+            &false,
+            &0,
+            &-1,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        // The recurring successor carries the same late fee
+        client.pay_bill(&owner, &bill_id);
+        let next = client.get_unpaid_bills(&owner).get(0).unwrap();
+        assert_eq!(next.late_fee, 75);
+    }
+
+    #[test]
+    fn test_pay_bill_usdc_charges_late_fee_only_when_late() {
+        use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        let admin = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        let treasury = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        set_time(&env, 500_000);
+        client.set_pause_admin(&admin, &admin);
+        client.set_treasury(&admin, &treasury);
+        let usdc = env.register_stellar_asset_contract_v2(admin);
+        StellarAssetClient::new(&env, &usdc.address()).mint(&owner, &10_000);
+        let token = TokenClient::new(&env, &usdc.address());
+
+        let on_time = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1000,
+            &1_000_000,
+            &false,
+            &0,
+            &75,
+        );
+        let late = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &200,
+            &600_000,
+            &false,
+            &0,
+            &25,
+        );
+
+        set_time(&env, 700_000);
+        assert_eq!(
+            client.pay_bill_usdc(&owner, &on_time, &usdc.address()),
+            1000
+        );
+        assert_eq!(client.pay_bill_usdc(&owner, &late, &usdc.address()), 225);
+        assert_eq!(token.balance(&treasury), 1225);
+        assert_eq!(token.balance(&owner), 8775);
+    }
+
+    #[test]
+    fn test_max_occurrences_stops_recurring_chain() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        set_time(&env, 500_000);
+        let first = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Gym"),
+            &300,
+            &1_000_000,
+            &true,
+            &30,
+            &0,
+        );
+        client.set_max_occurrences(&owner, &first, &2);
+        assert_eq!(client.get_occurrence_count(&first), 1);
+
+        client.pay_bill(&owner, &first);
+        let second = client.get_unpaid_bills(&owner).get(0).unwrap();
+        assert_eq!(second.max_occurrences, 2);
+        assert_eq!(client.get_occurrence_count(&second.id), 2);
+
+        // Second occurrence reached the cap: paid, but no third bill
+        client.pay_bill(&owner, &second.id);
+        assert!(client.get_bill(&second.id).unwrap().paid);
+        assert_eq!(client.get_unpaid_bills(&owner).len(), 0);
+    }
+
+    #[test]
+    fn test_peek_next_bill_id_matches_created_id() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        let peeked = client.peek_next_bill_id();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &100,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        assert_eq!(bill_id, peeked);
+        assert_eq!(client.peek_next_bill_id(), bill_id + 1);
+    }
+
+    #[test]
+    fn test_export_import_bills_round_trip_per_owner() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let source_id = env.register_contract(None, BillPayments);
+        let source = BillPaymentsClient::new(&env, &source_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        let other = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        let rent = source.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1200,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        source.create_bill(
+            &other,
+            &String::from_str(&env, "Other"),
+            &50,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        let phone = source.create_bill(
+            &owner,
+            &String::from_str(&env, "Phone"),
+            &40,
+            &2_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        source.pay_bill(&owner, &phone);
+
+        let snapshot = source.export_bills(&owner);
+        assert_eq!(snapshot.bills.len(), 2);
+        assert_eq!(snapshot.next_id, 3);
+        for bill in snapshot.bills.iter() {
+            assert_eq!(bill.owner, owner);
+        }
+
+        let target_id = env.register_contract(None, BillPayments);
+        let target = BillPaymentsClient::new(&env, &target_id);
+
+        let mut tampered = snapshot.clone();
+        tampered.checksum = tampered.checksum.wrapping_add(1);
+        let result = target.try_import_bills(&owner, &0, &tampered);
+        assert_eq!(result, Err(Ok(Error::SnapshotChecksumMismatch)));
+        let result = target.try_import_bills(&other, &0, &snapshot);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(target.import_bills(&owner, &0, &snapshot), 2);
+        assert_eq!(target.get_nonce(&owner), 1);
+        assert_eq!(target.get_bill(&rent).unwrap().amount, 1200);
+        assert!(target.get_bill(&phone).unwrap().paid);
+        assert!(target.get_bill(&2).is_none());
+        assert_eq!(target.peek_next_bill_id(), 4);
+
+        let result = target.try_import_bills(&owner, &0, &snapshot);
+        assert_eq!(result, Err(Ok(Error::InvalidNonce)));
+    }
+
+    #[test]
+    fn test_import_bills_derives_next_id_from_imported_bills() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1200,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        let mut snapshot = client.export_bills(&owner);
+        let mut bill = snapshot.bills.get(0).unwrap();
+
+        // A forged next_id is ignored; a bill id far past NEXT_ID moves it forward
+        bill.id = 10;
+        snapshot.bills = soroban_sdk::vec![&env, bill.clone()];
+        snapshot.next_id = u32::MAX;
+        snapshot.checksum = BillPayments::compute_bills_checksum(
+            snapshot.version,
+            snapshot.next_id,
+            &snapshot.bills,
+        );
+        assert_eq!(client.import_bills(&owner, &0, &snapshot), 1);
+        assert_eq!(client.peek_next_bill_id(), 11);
+        let next = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Phone"),
+            &40,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        assert_eq!(next, 11);
+        assert_eq!(client.get_bill(&10).unwrap().amount, 1200);
+
+        bill.id = u32::MAX;
+        snapshot.bills = soroban_sdk::vec![&env, bill];
+        snapshot.checksum = BillPayments::compute_bills_checksum(
+            snapshot.version,
+            snapshot.next_id,
+            &snapshot.bills,
+        );
+        let result = client.try_import_bills(&owner, &1, &snapshot);
+        assert_eq!(result, Err(Ok(Error::InvalidBillId)));
+    }
+
+    #[test]
+    fn test_reschedule_bill_changes_successor_interval() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+        env.mock_all_auths();
+        set_time(&env, 500_000);
+        let subscription = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Streaming"),
+            &1_200,
+            &1_000_000,
+            &true,
+            &30,
+            &0,
+        );
+        let one_off = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Repair"),
+            &500,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+
+        let result = client.try_reschedule_bill(&owner, &subscription, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+        let result = client.try_reschedule_bill(&owner, &one_off, &365);
+        assert_eq!(result, Err(Ok(Error::BillNotRecurring)));
+
+        // Monthly -> annual
+        client.reschedule_bill(&owner, &subscription, &365);
+        assert_eq!(client.get_bill(&subscription).unwrap().frequency_days, 365);
+
+        client.pay_bill(&owner, &subscription);
+        let unpaid = client.get_unpaid_bills(&owner);
+        let next = unpaid.iter().find(|b| b.id != one_off).unwrap();
+        assert_eq!(next.due_date, 1_000_000 + 365 * 86400);
+        assert_eq!(next.frequency_days, 365);
+    }
+
+    #[test]
+    fn test_has_overdue_bills_and_count() {
+        let env = Env::default();
+        set_time(&env, 1_000_000);
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        let other = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        env.mock_all_auths();
+
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Late"),
+            &100,
+            &1_500_000,
+            &false,
+            &0,
+            &0,
+        );
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Upcoming"),
+            &200,
+            &3_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        client.create_bill(
+            &other,
+            &String::from_str(&env, "Upcoming"),
+            &200,
+            &3_000_000,
+            &false,
+            &0,
+            &0,
+        );
+
+        assert!(client.has_overdue_bills(&owner, &2_000_000));
+        assert_eq!(client.overdue_count(&owner, &2_000_000), 1);
+
+        assert!(!client.has_overdue_bills(&other, &2_000_000));
+        assert_eq!(client.overdue_count(&other, &2_000_000), 0);
+    }
+
+    #[test]
+    fn test_bump_many_needs_no_auth_and_keeps_bills() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+        env.mock_all_auths();
+
+        let first = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1000,
+            &1_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        let second = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &200,
+            &2_000_000,
+            &true,
+            &30,
+            &0,
+        );
+        client.pay_bill(&owner, &second);
+        let before = client.get_all_bills_for_owner(&owner);
+
+        // Let the instance entry (and the BILLS map in it) age below the bump threshold
+        env.ledger().with_mut(|li| {
+            li.sequence_number += INSTANCE_BUMP_AMOUNT - INSTANCE_LIFETIME_THRESHOLD + 1
+        });
+        let touched = client.bump_many(&Vec::from_array(&env, [first, second, 999]));
+        assert_eq!(touched, 2);
+        assert!(env.auths().is_empty());
+        let ttl = env.as_contract(&contract_id, || {
+            soroban_sdk::testutils::storage::Instance::get_ttl(&env.storage().instance())
+        });
+        assert_eq!(ttl, INSTANCE_BUMP_AMOUNT);
+
+        let after = client.get_all_bills_for_owner(&owner);
+        assert_eq!(after.len(), before.len());
+        for (old, new) in before.iter().zip(after.iter()) {
+            assert_eq!(old.id, new.id);
+            assert_eq!(old.amount, new.amount);
+            assert_eq!(old.paid, new.paid);
+            assert_eq!(old.due_date, new.due_date);
+        }
+
+        let mut too_many = Vec::new(&env);
+        for id in 0..=MAX_BATCH_SIZE {
+            too_many.push_back(id);
+        }
+        assert_eq!(
+            client.try_bump_many(&too_many),
+            Err(Ok(Error::BatchTooLarge))
+        );
+    }
 }
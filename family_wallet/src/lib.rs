@@ -1737,6 +1737,12 @@ impl FamilyWallet {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Extend instance storage TTL without mutating wallet state. Anyone may call it.
+    pub fn bump_ttl(env: Env) -> bool {
+        Self::extend_instance_ttl(&env);
+        true
+    }
+
     /// Extend the TTL of archive storage with longer duration
     fn extend_archive_ttl(env: &Env) {
         env.storage()
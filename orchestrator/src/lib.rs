@@ -53,8 +53,8 @@
 //! ```
 
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
-    Env, Map, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, Address, Env, Map, Symbol, Vec,
 };
 
 #[cfg(test)]
@@ -143,6 +143,18 @@ pub trait BillPaymentsTrait {
     /// # Gas Estimation
     /// ~4000 gas
     fn pay_bill(env: Env, caller: Address, bill_id: u32);
+
+    /// Pay a bill in tokens to the bills treasury, late fee included
+    ///
+    /// # Returns
+    /// The amount transferred
+    fn pay_bill_usdc(env: Env, caller: Address, bill_id: u32, usdc_contract: Address) -> i128;
+
+    /// The owner's unpaid bills as `(bill_id, amount owed at now_ts)`
+    fn get_amounts_owed(env: Env, owner: Address, now_ts: u64) -> Vec<(u32, i128)>;
+
+    /// Recipient of `pay_bill_usdc` payments, if configured
+    fn get_treasury(env: Env) -> Option<Address>;
 }
 
 /// Insurance contract client interface
@@ -163,6 +175,15 @@ pub trait InsuranceTrait {
     /// # Gas Estimation
     /// ~4000 gas
     fn pay_premium(env: Env, caller: Address, policy_id: u32) -> bool;
+
+    /// Pay a policy's premium in tokens to the policy's payee (or the treasury)
+    fn pay_premium_usdc(env: Env, caller: Address, policy_id: u32, usdc_contract: Address) -> bool;
+
+    /// The owner's active policies due at `now_ts`, as `(policy_id, monthly_premium)`
+    fn get_due_premiums(env: Env, owner: Address, now_ts: u64) -> Vec<(u32, i128)>;
+
+    /// Recipient of `pay_premium_usdc` payments for `policy_id`, if any
+    fn get_payee(env: Env, policy_id: u32) -> Option<Address>;
 }
 
 /// Orchestrator-specific errors
@@ -188,6 +209,12 @@ pub enum OrchestratorError {
     InvalidContractAddress = 8,
     /// Generic cross-contract call failure
     CrossContractCallFailed = 9,
+    /// Nonce does not match the caller's current nonce
+    InvalidNonce = 10,
+    /// Caller's token balance does not cover the full settlement
+    InsufficientFunds = 11,
+    /// A settlement payment would go to someone other than the expected payee
+    PayeeMismatch = 12,
 }
 
 /// Result of a complete remittance flow execution
@@ -214,6 +241,18 @@ pub struct RemittanceFlowResult {
     pub timestamp: u64,
 }
 
+/// Result of a `settle_month` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementSummary {
+    /// Number of bills paid
+    pub bills_paid: u32,
+    /// Number of insurance premiums paid
+    pub premiums_paid: u32,
+    /// Total tokens transferred to the payee
+    pub total_paid: i128,
+}
+
 /// Event emitted on successful remittance flow completion
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1138,6 +1177,150 @@ impl Orchestrator {
         env.storage().instance().set(&symbol_short!("AUDIT"), &log);
     }
 
+    // ============================================================================
+    // Monthly Settlement
+    // ============================================================================
+
+    /// Pay all of the caller's unpaid bills and due premiums in one call
+    ///
+    /// Each bill is paid with `pay_bill_usdc` (to the bills treasury) and each premium
+    /// with `pay_premium_usdc` (to the policy's payee), all in `usdc_contract` tokens.
+    /// Every payment must go to `payee`, otherwise nothing is paid. If the caller's
+    /// balance does not cover everything, nothing is paid; if any payment fails, the
+    /// whole settlement reverts.
+    ///
+    /// # Arguments
+    /// * `caller` - Address settling its obligations (must authorize)
+    /// * `nonce` - Must equal `get_nonce(caller)`; consumed on success
+    /// * `usdc_contract` - Token the obligations are paid in
+    /// * `payee` - Recipient the caller expects every payment to reach
+    /// * `bills_addr` - Address of the Bill Payments contract
+    /// * `insurance_addr` - Address of the Insurance contract
+    ///
+    /// # Returns
+    /// Ok(SettlementSummary) with the counts paid and the total transferred
+    pub fn settle_month(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        usdc_contract: Address,
+        payee: Address,
+        bills_addr: Address,
+        insurance_addr: Address,
+    ) -> Result<SettlementSummary, OrchestratorError> {
+        caller.require_auth();
+        if nonce != Self::get_nonce(env.clone(), caller.clone()) {
+            return Err(OrchestratorError::InvalidNonce);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let bills_client = BillPaymentsClient::new(&env, &bills_addr);
+        let insurance_client = InsuranceClient::new(&env, &insurance_addr);
+        let bills = bills_client.get_amounts_owed(&caller, &timestamp);
+        let premiums = insurance_client.get_due_premiums(&caller, &timestamp);
+
+        let bills_to_payee = bills.is_empty() || bills_client.get_treasury() == Some(payee.clone());
+        let premiums_to_payee = premiums
+            .iter()
+            .all(|(policy_id, _)| insurance_client.get_payee(&policy_id) == Some(payee.clone()));
+        if !bills_to_payee || !premiums_to_payee {
+            Self::emit_error_event(
+                &env,
+                &caller,
+                symbol_short!("settle"),
+                OrchestratorError::PayeeMismatch as u32,
+                timestamp,
+            );
+            Self::append_audit_entry(
+                &env,
+                &caller,
+                symbol_short!("settle"),
+                0,
+                false,
+                Some(OrchestratorError::PayeeMismatch as u32),
+            );
+            return Err(OrchestratorError::PayeeMismatch);
+        }
+
+        let mut total_owed: i128 = 0;
+        for (_, amount) in bills.iter().chain(premiums.iter()) {
+            total_owed = total_owed
+                .checked_add(amount)
+                .ok_or(OrchestratorError::InvalidAmount)?;
+        }
+
+        let token = TokenClient::new(&env, &usdc_contract);
+        if token.balance(&caller) < total_owed {
+            Self::emit_error_event(
+                &env,
+                &caller,
+                symbol_short!("settle"),
+                OrchestratorError::InsufficientFunds as u32,
+                timestamp,
+            );
+            Self::append_audit_entry(
+                &env,
+                &caller,
+                symbol_short!("settle"),
+                total_owed,
+                false,
+                Some(OrchestratorError::InsufficientFunds as u32),
+            );
+            return Err(OrchestratorError::InsufficientFunds);
+        }
+
+        let mut total_paid: i128 = 0;
+        for (bill_id, _) in bills.iter() {
+            let paid = bills_client.pay_bill_usdc(&caller, &bill_id, &usdc_contract);
+            total_paid = total_paid
+                .checked_add(paid)
+                .ok_or(OrchestratorError::InvalidAmount)?;
+        }
+        for (policy_id, premium) in premiums.iter() {
+            if !insurance_client.pay_premium_usdc(&caller, &policy_id, &usdc_contract) {
+                return Err(OrchestratorError::InsurancePaymentFailed);
+            }
+            total_paid = total_paid
+                .checked_add(premium)
+                .ok_or(OrchestratorError::InvalidAmount)?;
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(&env));
+        nonces.set(caller.clone(), nonce + 1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+
+        Self::append_audit_entry(
+            &env,
+            &caller,
+            symbol_short!("settle"),
+            total_paid,
+            true,
+            None,
+        );
+        let summary = SettlementSummary {
+            bills_paid: bills.len(),
+            premiums_paid: premiums.len(),
+            total_paid,
+        };
+        env.events()
+            .publish((symbol_short!("settle"),), summary.clone());
+        Ok(summary)
+    }
+
+    /// Current nonce for `address`, to pass to `settle_month`
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces.and_then(|n| n.get(address)).unwrap_or(0)
+    }
+
     /// Get current execution statistics
     ///
     /// # Returns
@@ -1189,4 +1372,10 @@ impl Orchestrator {
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
+
+    /// Keep the orchestrator instance alive without touching state; no auth required.
+    pub fn bump_ttl(env: Env) -> bool {
+        Self::extend_instance_ttl(&env);
+        true
+    }
 }
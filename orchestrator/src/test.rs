@@ -1,6 +1,6 @@
 // Integration tests for the orchestrator contract
 
-use crate::{Orchestrator, OrchestratorClient, OrchestratorError};
+use crate::{Orchestrator, OrchestratorClient, OrchestratorError, SettlementSummary};
 use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Vec};
 
 // ============================================================================
@@ -520,4 +520,118 @@ mod tests {
 
         assert_eq!(log.len(), 0);
     }
+
+    #[test]
+    fn test_settle_month_routes_payments_through_real_contracts() {
+        use bill_payments::{BillPayments, BillPaymentsClient as BillsClient};
+        use insurance::{Insurance, InsuranceClient as PoliciesClient};
+        use soroban_sdk::testutils::Ledger;
+        use soroban_sdk::token::{StellarAssetClient, TokenClient};
+        use soroban_sdk::String;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let orchestrator_id = env.register_contract(None, Orchestrator);
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        let bills_id = env.register_contract(None, BillPayments);
+        let bills = BillsClient::new(&env, &bills_id);
+        let insurance_id = env.register_contract(None, Insurance);
+        let insurance = PoliciesClient::new(&env, &insurance_id);
+
+        let user = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let provider = Address::generate(&env);
+        let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+        let token = TokenClient::new(&env, &usdc.address());
+        StellarAssetClient::new(&env, &usdc.address()).mint(&user, &550);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+        bills.set_pause_admin(&admin, &admin);
+        bills.set_treasury(&admin, &provider);
+        let rent = bills.create_bill(
+            &user,
+            &String::from_str(&env, "Rent"),
+            &300,
+            &5_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        let power = bills.create_bill(
+            &user,
+            &String::from_str(&env, "Power"),
+            &200,
+            &5_000_000,
+            &false,
+            &0,
+            &0,
+        );
+        let policy_id = insurance.create_policy_with_payee(
+            &user,
+            &String::from_str(&env, "Health"),
+            &String::from_str(&env, "health"),
+            &100,
+            &10_000,
+            &provider,
+        );
+        // Move past the first billing period so the premium is due
+        env.ledger()
+            .with_mut(|li| li.timestamp = 1_000_000 + 31 * 86400);
+
+        // Payments that would reach anyone but the expected payee are refused
+        let other = Address::generate(&env);
+        let result =
+            client.try_settle_month(&user, &0, &usdc.address(), &other, &bills_id, &insurance_id);
+        assert_eq!(result, Err(Ok(OrchestratorError::PayeeMismatch)));
+
+        // 300 + 200 in bills and a 100 premium is more than the user holds
+        let result = client.try_settle_month(
+            &user,
+            &0,
+            &usdc.address(),
+            &provider,
+            &bills_id,
+            &insurance_id,
+        );
+        assert_eq!(result, Err(Ok(OrchestratorError::InsufficientFunds)));
+        assert!(!bills.get_bill(&rent).unwrap().paid);
+
+        StellarAssetClient::new(&env, &usdc.address()).mint(&user, &50);
+        let summary = client.settle_month(
+            &user,
+            &0,
+            &usdc.address(),
+            &provider,
+            &bills_id,
+            &insurance_id,
+        );
+        assert_eq!(
+            summary,
+            SettlementSummary {
+                bills_paid: 2,
+                premiums_paid: 1,
+                total_paid: 600,
+            }
+        );
+        assert_eq!(token.balance(&provider), 600);
+        assert_eq!(token.balance(&user), 0);
+        assert!(bills.get_bill(&rent).unwrap().paid);
+        assert!(bills.get_bill(&power).unwrap().paid);
+        assert!(insurance
+            .get_policy(&policy_id)
+            .unwrap()
+            .last_paid_at
+            .is_some());
+        assert_eq!(client.get_nonce(&user), 1);
+
+        let result = client.try_settle_month(
+            &user,
+            &0,
+            &usdc.address(),
+            &provider,
+            &bills_id,
+            &insurance_id,
+        );
+        assert_eq!(result, Err(Ok(OrchestratorError::InvalidNonce)));
+    }
 }
@@ -1,6 +1,9 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::{StellarAssetClient, TokenClient},
+    vec,
+    xdr::ToXdr,
     Address, Env, Map, Symbol, Vec,
 };
 
@@ -34,16 +37,54 @@ pub enum RemittanceSplitError {
     ChecksumMismatch = 9,
     InvalidDueDate = 10,
     ScheduleNotFound = 11,
+    UnsupportedChecksumAlgo = 12,
+    DistributionsFrozen = 13,
+    InvalidFreezeWindow = 14,
+    AliasNotFound = 15,
+    ReadOnly = 16,
+    DistributionsPaused = 17,
+    InvalidFeeBps = 18,
+    InvalidCategory = 19,
+    ResetDisabled = 20,
+    BelowCategoryMinimum = 21,
+    ContractPaused = 22,
+    /// Snapshot was signed with a checksum algorithm that is no longer accepted
+    ChecksumOutdated = 23,
+    InvalidWeights = 24,
+    RecipientCannotReceive = 25,
+    DailyCapExceeded = 26,
+    AccountsNotSet = 27,
+    /// The owner stored recipients with `set_accounts`; others are rejected
+    RecipientsLocked = 28,
+    InvalidHistoryIndex = 29,
 }
 
-#[derive(Clone)]
+/// Savings Goals totals read by `get_net_position`
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsTrait {
+    fn get_total_saved(env: Env, owner: Address) -> i128;
+}
+
+/// Bill Payments totals read by `get_net_position`
+#[contractclient(name = "BillPaymentsClient")]
+pub trait BillPaymentsTrait {
+    fn get_total_unpaid(env: Env, owner: Address) -> i128;
+}
+
+/// Insurance totals read by `get_net_position`
+#[contractclient(name = "InsuranceClient")]
+pub trait InsuranceTrait {
+    fn get_total_monthly_premium(env: Env, owner: Address) -> i128;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Allocation {
     pub category: Symbol,
     pub amount: i128,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct AccountGroup {
     pub spending: Address,
@@ -56,6 +97,11 @@ pub struct AccountGroup {
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
 
+/// TTL for the audit chunks and received totals, which live in persistent storage and
+/// outlast the instance
+const AUDIT_LIFETIME_THRESHOLD: u32 = 518400; // ~30 days
+const AUDIT_BUMP_AMOUNT: u32 = 3110400; // ~180 days
+
 /// Split configuration with owner tracking for access control
 #[derive(Clone)]
 #[contracttype]
@@ -67,6 +113,14 @@ pub struct SplitConfig {
     pub insurance_percent: u32,
     pub timestamp: u64,
     pub initialized: bool,
+    /// Platform fee in basis points taken by `distribute_usdc` before splitting
+    pub fee_bps: u32,
+    pub fee_collector: Option<Address>,
+    /// Non-spending buckets below this amount are folded into spending by
+    /// `distribute_usdc` instead of being transferred (0 disables)
+    pub min_transfer: i128,
+    /// Let `distribute_usdc` pay several categories to the same address
+    pub allow_duplicate_recipients: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -87,17 +141,48 @@ pub enum SplitEvent {
     Initialized,
     Updated,
     Calculated,
+    OwnershipTransferred,
+    ConfigReset,
 }
 
 /// Snapshot for data export/import (migration). Checksum is a simple numeric digest for on-chain verification.
+/// `checksum_algo` selects how the checksum was computed, independently of the data `version`.
 #[contracttype]
 #[derive(Clone)]
 pub struct ExportSnapshot {
     pub version: u32,
+    pub checksum_algo: u32,
     pub checksum: u64,
     pub config: SplitConfig,
 }
 
+/// Recipients and amounts of one `distribute_usdc` call, for reconciliation.
+#[contracttype]
+#[derive(Clone)]
+pub struct DistributionRecord {
+    pub timestamp: u64,
+    pub total: i128,
+    pub spending_to: Address,
+    pub savings_to: Address,
+    pub bills_to: Address,
+    pub insurance_to: Address,
+    /// [spending, savings, bills, insurance]
+    pub amounts: Vec<i128>,
+}
+
+/// What one successful `distribute_usdc` call actually moved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionReceipt {
+    pub total_amount: i128,
+    /// Per-category amounts transferred, plus a `FEE` entry when a platform fee was
+    /// taken and an `OVERFLOW` entry for any excess over the category caps left with
+    /// the sender, so the amounts always sum to `total_amount`
+    pub allocations: Vec<Allocation>,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
 /// Audit log entry for security and compliance.
 #[contracttype]
 #[derive(Clone)]
@@ -106,6 +191,25 @@ pub struct AuditEntry {
     pub caller: Address,
     pub timestamp: u64,
     pub success: bool,
+    /// Amount moved by the operation; 0 for non-financial operations.
+    pub amount: i128,
+}
+
+/// Summary of an emitted event, kept so indexers can detect gaps by sequence number.
+#[contracttype]
+#[derive(Clone)]
+pub struct EventLog {
+    pub seq: u64,
+    pub kind: Symbol,
+    pub timestamp: u64,
+}
+
+/// Owner-set window `[start_ts, end_ts)` during which distributions are rejected.
+#[contracttype]
+#[derive(Clone)]
+pub struct FreezeWindow {
+    pub start_ts: u64,
+    pub end_ts: u64,
 }
 
 /// Schedule for automatic remittance splits
@@ -135,10 +239,50 @@ pub enum ScheduleEvent {
     Cancelled,
 }
 
-const SNAPSHOT_VERSION: u32 = 1;
+/// Snapshots hold basis points and an FNV-1a checksum. Versions 1 (whole
+/// percentages) and 2 (basis points) carry the legacy additive/rolling checksums
+/// and are still accepted on import.
+const SNAPSHOT_VERSION: u32 = 3;
+
+/// Split denominators: whole percentages, or basis points once `BPS_MODE` is set
+const PERCENT_DENOMINATOR: u32 = 100;
+const BPS_DENOMINATOR: u32 = 10_000;
+/// Legacy checksum: wrapping sum of the fields multiplied by 31 (order-insensitive).
+pub const CHECKSUM_ALGO_ADDITIVE: u32 = 1;
+/// Rolling polynomial hash over the fields (order-sensitive).
+pub const CHECKSUM_ALGO_ROLLING: u32 = 2;
+/// FNV-1a over the version, the owner's XDR bytes, positionally weighted fields and
+/// the platform fee settings.
+pub const CHECKSUM_ALGO_FNV: u32 = 3;
+const LATEST_CHECKSUM_ALGO: u32 = CHECKSUM_ALGO_FNV;
+/// Audit entries per persistent chunk, and the largest page `get_audit_log` returns
 const MAX_AUDIT_ENTRIES: u32 = 100;
+const MAX_DISTRIBUTION_RECORDS: u32 = 50;
+const MAX_EVENT_LOG_ENTRIES: u32 = 100;
+/// Split versions kept by `get_config_history`; older ones are dropped.
+const MAX_CONFIG_HISTORY: u32 = 50;
+/// Number of daily volume buckets retained; older days are dropped.
+const MAX_VOLUME_BUCKETS: u32 = 90;
+const SECONDS_PER_DAY: u64 = 86400;
+/// Width of a daily-cap window bucket; distributions in the same hour share one entry.
+const DAY_LOG_BUCKET: u64 = 3600;
+/// Most buckets the daily-cap window can hold: a full day plus the current hour.
+const MAX_DAY_LOG_BUCKETS: u32 = (SECONDS_PER_DAY / DAY_LOG_BUCKET) as u32 + 1;
 const CONTRACT_VERSION: u32 = 1;
 
+/// Platform fee collector and the amount it receives from one distribution
+type FeeTransfer = (Address, i128);
+
+/// What one distribution moves, as computed by `plan_distribution`
+struct DistributionPlan {
+    fee: Option<FeeTransfer>,
+    /// [spending, savings, bills, insurance]
+    amounts: Vec<i128>,
+    /// Amount above the category caps with no uncapped category to take it; it
+    /// stays with the sender
+    overflow: i128,
+}
+
 #[contract]
 pub struct RemittanceSplit;
 
@@ -155,7 +299,7 @@ impl RemittanceSplit {
     }
     fn require_not_paused(env: &Env) -> Result<(), RemittanceSplitError> {
         if Self::get_global_paused(env) {
-            Err(RemittanceSplitError::Unauthorized)
+            Err(RemittanceSplitError::ContractPaused)
         } else {
             Ok(())
         }
@@ -180,8 +324,9 @@ impl RemittanceSplit {
             .set(&symbol_short!("PAUSE_ADM"), &new_admin);
         Ok(())
     }
-    pub fn pause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
+    pub fn pause(env: Env, caller: Address, nonce: u64) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
         let config: SplitConfig = env
             .storage()
             .instance()
@@ -189,17 +334,22 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
         if admin != caller {
+            Self::append_audit(&env, symbol_short!("pause"), &caller, false);
             return Err(RemittanceSplitError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED"), &true);
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("pause"), &caller, true);
         env.events()
             .publish((symbol_short!("split"), symbol_short!("paused")), ());
+        Self::record_event(&env, symbol_short!("paused"));
         Ok(())
     }
-    pub fn unpause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
+    pub fn unpause(env: Env, caller: Address, nonce: u64) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
         let config: SplitConfig = env
             .storage()
             .instance()
@@ -207,18 +357,69 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
         if admin != caller {
+            Self::append_audit(&env, symbol_short!("unpause"), &caller, false);
             return Err(RemittanceSplitError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED"), &false);
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("unpause"), &caller, true);
         env.events()
             .publish((symbol_short!("split"), symbol_short!("unpaused")), ());
+        Self::record_event(&env, symbol_short!("unpaused"));
         Ok(())
     }
     pub fn is_paused(env: Env) -> bool {
         Self::get_global_paused(&env)
     }
+
+    /// Block only money movement (`distribute_usdc` and its variants) while
+    /// `update_split` and reads keep working. Owner only.
+    pub fn pause_distributions(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
+        Self::set_distributions_paused(&env, &caller, true)
+    }
+
+    pub fn resume_distributions(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
+        Self::set_distributions_paused(&env, &caller, false)
+    }
+
+    pub fn is_distributions_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("DIST_PAUS"))
+            .unwrap_or(false)
+    }
+
+    fn set_distributions_paused(
+        env: &Env,
+        caller: &Address,
+        paused: bool,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != *caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DIST_PAUS"), &paused);
+        let topic = if paused {
+            symbol_short!("dist_paus")
+        } else {
+            symbol_short!("dist_res")
+        };
+        env.events()
+            .publish((symbol_short!("split"), topic.clone()), ());
+        Self::record_event(env, topic);
+        Ok(())
+    }
     pub fn get_version(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -247,6 +448,98 @@ impl RemittanceSplit {
             .set(&symbol_short!("UPG_ADM"), &new_admin);
         Ok(())
     }
+    /// Arm (or disarm) `reset` for staging deployments. Upgrade admin only.
+    pub fn set_reset_enabled(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
+        if admin != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("RST_EN"), &enabled);
+        Ok(())
+    }
+
+    pub fn is_reset_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("RST_EN"))
+            .unwrap_or(false)
+    }
+
+    /// Wipe the split config, nonces, audit log and all per-deployment history so
+    /// `initialize_split` can run again. Meant for test and staging only:
+    /// - caller must be the upgrade admin
+    /// - `set_reset_enabled(true)` must have been called first, and reset disarms it
+    ///   again, so every reset needs its own explicit opt-in
+    /// - a contract past its read-only cutoff can never be reset
+    ///
+    /// Admin roles, version, pause state and the read-only cutoff are kept.
+    pub fn reset(env: Env, caller: Address) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
+        if admin != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if !Self::is_reset_enabled(env.clone()) {
+            return Err(RemittanceSplitError::ResetDisabled);
+        }
+        Self::require_writable(&env)?;
+
+        let storage = env.storage().instance();
+        for key in [
+            symbol_short!("CONFIG"),
+            symbol_short!("SPLIT"),
+            symbol_short!("CAT_NAMES"),
+            symbol_short!("BPS_MODE"),
+            symbol_short!("ACCOUNTS"),
+            symbol_short!("NONCES"),
+            symbol_short!("DISTRIBS"),
+            symbol_short!("OPERATORS"),
+            symbol_short!("ALIASES"),
+            symbol_short!("CAT_CAPS"),
+            symbol_short!("MIN_CAT"),
+            symbol_short!("DIST_PAUS"),
+            symbol_short!("FREEZE"),
+            symbol_short!("VOL_DAY"),
+            symbol_short!("DAY_CAP"),
+            symbol_short!("DAY_LOG"),
+            symbol_short!("REM_SCH"),
+            symbol_short!("NEXT_RSCH"),
+            symbol_short!("SCH_DIST"),
+            symbol_short!("EVT_LOG"),
+            symbol_short!("EVT_SEQ"),
+            symbol_short!("CFG_HIST"),
+            symbol_short!("RST_EN"),
+        ] {
+            storage.remove(&key);
+        }
+        let chunks = Self::get_audit_count(env.clone()).div_ceil(MAX_AUDIT_ENTRIES);
+        for chunk in 0..chunks {
+            env.storage()
+                .persistent()
+                .remove(&(symbol_short!("AUDIT"), chunk));
+        }
+        env.storage().instance().remove(&symbol_short!("AUDIT_CNT"));
+        let epoch: u32 = storage.get(&symbol_short!("RCV_EPOCH")).unwrap_or(0);
+        storage.set(&symbol_short!("RCV_EPOCH"), &epoch.saturating_add(1));
+        Self::extend_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("reset")), caller);
+        Self::record_event(&env, symbol_short!("reset"));
+        Ok(true)
+    }
+
+    pub fn is_initialized(env: Env) -> bool {
+        Self::require_initialized(&env).is_ok()
+    }
+
     pub fn set_version(
         env: Env,
         caller: Address,
@@ -270,6 +563,7 @@ impl RemittanceSplit {
             (symbol_short!("split"), symbol_short!("upgraded")),
             (prev, new_version),
         );
+        Self::record_event(&env, symbol_short!("upgraded"));
         Ok(())
     }
 
@@ -299,6 +593,110 @@ impl RemittanceSplit {
         savings_percent: u32,
         bills_percent: u32,
         insurance_percent: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        let categories = Self::default_categories(
+            &env,
+            [
+                spending_percent,
+                savings_percent,
+                bills_percent,
+                insurance_percent,
+            ],
+        );
+        Self::initialize_split_dynamic(env, owner, nonce, categories)
+    }
+
+    /// Same as `initialize_split`, also setting the platform fee `distribute_usdc`
+    /// sends to `fee_collector` before splitting the rest (0 disables it).
+    ///
+    /// # Panics
+    /// - If owner doesn't authorize the transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_split_with_fee(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+        fee_bps: u32,
+        fee_collector: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        let categories = Self::default_categories(
+            &env,
+            [
+                spending_percent,
+                savings_percent,
+                bills_percent,
+                insurance_percent,
+            ],
+        );
+        Self::init_split(
+            env,
+            owner,
+            nonce,
+            categories,
+            PERCENT_DENOMINATOR,
+            (fee_bps, Some(fee_collector)),
+        )
+    }
+
+    /// Initialize the split in basis points (10000 = 100%), e.g. 1250 for 12.5%.
+    ///
+    /// The four values must sum to 10000. Afterwards the contract stays in basis-points
+    /// mode: `update_split`, `get_split` and `get_config` use basis points too.
+    ///
+    /// # Panics
+    /// - If owner doesn't authorize the transaction
+    pub fn initialize_split_bps(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        spending_bps: u32,
+        savings_bps: u32,
+        bills_bps: u32,
+        insurance_bps: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        let categories =
+            Self::default_categories(&env, [spending_bps, savings_bps, bills_bps, insurance_bps]);
+        Self::init_split(env, owner, nonce, categories, BPS_DENOMINATOR, (0, None))
+    }
+
+    /// Initialize the split with any number of named categories.
+    ///
+    /// Percentages must sum to 100 and names must be unique. `calculate_split_dynamic`
+    /// splits by this list, with the last category absorbing rounding. The four-field
+    /// API (`calculate_split`, `distribute_usdc`) only works while exactly four
+    /// categories are configured.
+    ///
+    /// # Panics
+    /// - If owner doesn't authorize the transaction
+    pub fn initialize_split_dynamic(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        categories: Vec<(Symbol, u32)>,
+    ) -> Result<bool, RemittanceSplitError> {
+        Self::init_split(
+            env,
+            owner,
+            nonce,
+            categories,
+            PERCENT_DENOMINATOR,
+            (0, None),
+        )
+    }
+
+    /// Store a new split whose values are out of `denominator`, with `fee` as the
+    /// platform fee basis points and collector
+    fn init_split(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        categories: Vec<(Symbol, u32)>,
+        denominator: u32,
+        fee: (u32, Option<Address>),
     ) -> Result<bool, RemittanceSplitError> {
         owner.require_auth();
         Self::require_not_paused(&env)?;
@@ -310,46 +708,197 @@ impl RemittanceSplit {
             return Err(RemittanceSplitError::AlreadyInitialized);
         }
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        if let Err(e) = Self::validate_categories(&categories, denominator) {
             Self::append_audit(&env, symbol_short!("init"), &owner, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+            return Err(e);
+        }
+        let (fee_bps, fee_collector) = fee;
+        if fee_bps > BPS_DENOMINATOR {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(RemittanceSplitError::InvalidFeeBps);
         }
 
         Self::extend_instance_ttl(&env);
 
+        let percent = |i: u32| categories.get(i).map(|(_, p)| p).unwrap_or(0);
         let config = SplitConfig {
             owner: owner.clone(),
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
+            spending_percent: percent(0),
+            savings_percent: percent(1),
+            bills_percent: percent(2),
+            insurance_percent: percent(3),
             timestamp: env.ledger().timestamp(),
             initialized: true,
+            fee_bps,
+            fee_collector,
+            min_transfer: 0,
+            allow_duplicate_recipients: false,
         };
 
         env.storage()
             .instance()
             .set(&symbol_short!("CONFIG"), &config);
+        Self::store_categories(&env, &categories);
         env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
+            &symbol_short!("BPS_MODE"),
+            &(denominator == BPS_DENOMINATOR),
         );
 
         Self::increment_nonce(&env, &owner)?;
         Self::append_audit(&env, symbol_short!("init"), &owner, true);
         env.events()
             .publish((symbol_short!("split"), SplitEvent::Initialized), owner);
+        Self::record_event(&env, symbol_short!("init"));
 
         Ok(true)
     }
 
+    /// Configured categories and their percentages, in split order. Defaults to the
+    /// four standard categories when only a legacy `SPLIT` vector is stored.
+    pub fn get_split_categories(env: Env) -> Vec<(Symbol, u32)> {
+        let split = Self::get_split(&env);
+        let names: Option<Vec<Symbol>> = env.storage().instance().get(&symbol_short!("CAT_NAMES"));
+        let mut result = Vec::new(&env);
+        match names {
+            Some(names) => {
+                for (name, percent) in names.iter().zip(split.iter()) {
+                    result.push_back((name, percent));
+                }
+            }
+            None => {
+                for (name, percent) in Self::split_categories().into_iter().zip(split.iter()) {
+                    result.push_back((name, percent));
+                }
+            }
+        }
+        result
+    }
+
+    /// Split `total_amount` across every configured category; the last one absorbs
+    /// the rounding remainder.
+    pub fn calculate_split_dynamic(
+        env: Env,
+        total_amount: i128,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        let categories = Self::get_split_categories(env.clone());
+        let amounts = Self::compute_split_amounts(
+            &env,
+            total_amount,
+            &Self::get_split(&env),
+            Self::split_denominator(&env),
+        )?;
+        let mut result = Vec::new(&env);
+        for ((category, _), amount) in categories.iter().zip(amounts.iter()) {
+            result.push_back(Allocation { category, amount });
+        }
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Calculated),
+            total_amount,
+        );
+        Self::record_event(&env, symbol_short!("calc"));
+
+        Ok(result)
+    }
+
+    fn default_categories(env: &Env, percents: [u32; 4]) -> Vec<(Symbol, u32)> {
+        let mut categories = Vec::new(env);
+        for (name, percent) in Self::split_categories().into_iter().zip(percents) {
+            categories.push_back((name, percent));
+        }
+        categories
+    }
+
+    fn validate_categories(
+        categories: &Vec<(Symbol, u32)>,
+        denominator: u32,
+    ) -> Result<(), RemittanceSplitError> {
+        let mut total: u64 = 0;
+        for (i, (name, percent)) in categories.iter().enumerate() {
+            if categories
+                .iter()
+                .skip(i + 1)
+                .any(|(other, _)| other == name)
+            {
+                return Err(RemittanceSplitError::InvalidCategory);
+            }
+            total += percent as u64;
+        }
+        if total != denominator as u64 {
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+        Ok(())
+    }
+
+    fn store_categories(env: &Env, categories: &Vec<(Symbol, u32)>) {
+        let mut names = Vec::new(env);
+        let mut split = Vec::new(env);
+        for (name, percent) in categories.iter() {
+            names.push_back(name);
+            split.push_back(percent);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_NAMES"), &names);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT"), &split);
+
+        let mut history = Self::get_config_history(env.clone());
+        if history.len() >= MAX_CONFIG_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(categories.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CFG_HIST"), &history);
+    }
+
+    /// Every split the contract has stored, oldest first (at most `MAX_CONFIG_HISTORY`)
+    pub fn get_config_history(env: Env) -> Vec<Vec<(Symbol, u32)>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CFG_HIST"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Categories whose percentage differs between two entries of `get_config_history`,
+    /// including categories present in only one of them. Categories of `index_a` come
+    /// first, in their stored order.
+    pub fn diff_config(
+        env: Env,
+        index_a: u32,
+        index_b: u32,
+    ) -> Result<Vec<Symbol>, RemittanceSplitError> {
+        let history = Self::get_config_history(env.clone());
+        let a = history
+            .get(index_a)
+            .ok_or(RemittanceSplitError::InvalidHistoryIndex)?;
+        let b = history
+            .get(index_b)
+            .ok_or(RemittanceSplitError::InvalidHistoryIndex)?;
+        let percent_in = |version: &Vec<(Symbol, u32)>, name: &Symbol| {
+            version.iter().find(|(n, _)| n == name).map(|(_, p)| p)
+        };
+
+        let mut changed = Vec::new(&env);
+        for (name, percent) in a.iter() {
+            if percent_in(&b, &name) != Some(percent) {
+                changed.push_back(name);
+            }
+        }
+        for (name, _) in b.iter() {
+            if percent_in(&a, &name).is_none() {
+                changed.push_back(name);
+            }
+        }
+        Ok(changed)
+    }
+
     pub fn update_split(
         env: Env,
         caller: Address,
@@ -359,9 +908,61 @@ impl RemittanceSplit {
         bills_percent: u32,
         insurance_percent: u32,
     ) -> Result<bool, RemittanceSplitError> {
+        Self::store_split_update(
+            env,
+            caller,
+            nonce,
+            [
+                spending_percent,
+                savings_percent,
+                bills_percent,
+                insurance_percent,
+            ],
+            None,
+        )
+    }
+
+    /// Same as `update_split`, also replacing the platform fee `distribute_usdc`
+    /// sends to `fee_collector` before splitting the rest (0 disables it).
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_split_with_fee(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+        fee_bps: u32,
+        fee_collector: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        Self::store_split_update(
+            env,
+            caller,
+            nonce,
+            [
+                spending_percent,
+                savings_percent,
+                bills_percent,
+                insurance_percent,
+            ],
+            Some((fee_bps, fee_collector)),
+        )
+    }
+
+    /// Replace the four percentages and, when `fee` is given, the platform fee
+    fn store_split_update(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        percents: [u32; 4],
+        fee: Option<(u32, Address)>,
+    ) -> Result<bool, RemittanceSplitError> {
+        let [spending_percent, savings_percent, bills_percent, insurance_percent] = percents;
         caller.require_auth();
         Self::require_not_paused(&env)?;
         Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
 
         let mut config: SplitConfig = env
             .storage()
@@ -374,11 +975,22 @@ impl RemittanceSplit {
             return Err(RemittanceSplitError::Unauthorized);
         }
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        let total = spending_percent as u64
+            + savings_percent as u64
+            + bills_percent as u64
+            + insurance_percent as u64;
+        if total != Self::split_denominator(&env) as u64 {
             Self::append_audit(&env, symbol_short!("update"), &caller, false);
             return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
         }
+        if let Some((fee_bps, fee_collector)) = fee {
+            if fee_bps > BPS_DENOMINATOR {
+                Self::append_audit(&env, symbol_short!("update"), &caller, false);
+                return Err(RemittanceSplitError::InvalidFeeBps);
+            }
+            config.fee_bps = fee_bps;
+            config.fee_collector = Some(fee_collector);
+        }
 
         Self::extend_instance_ttl(&env);
 
@@ -390,16 +1002,19 @@ impl RemittanceSplit {
         env.storage()
             .instance()
             .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
+        Self::store_categories(
+            &env,
+            &Self::default_categories(
                 &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
+                [
+                    spending_percent,
+                    savings_percent,
+                    bills_percent,
+                    insurance_percent,
+                ],
+            ),
         );
+        Self::increment_nonce(&env, &caller)?;
 
         let event = SplitInitializedEvent {
             spending_percent,
@@ -411,10 +1026,91 @@ impl RemittanceSplit {
         env.events().publish((SPLIT_INITIALIZED,), event);
         env.events()
             .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        Self::record_event(&env, symbol_short!("update"));
+
+        Ok(true)
+    }
+
+    /// Rewrite the stored `SPLIT` vector from the percentages in `CONFIG`, for the
+    /// case where the two have drifted apart (see `verify_integrity`). Owner only.
+    pub fn repair_split(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("repair"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if Self::get_split_categories(env.clone()).len() != 4 {
+            // CONFIG only mirrors the first four categories of a dynamic split
+            Self::append_audit(&env, symbol_short!("repair"), &caller, false);
+            return Err(RemittanceSplitError::InvalidCategory);
+        }
+
+        let total = config.spending_percent
+            + config.savings_percent
+            + config.bills_percent
+            + config.insurance_percent;
+        if total != Self::split_denominator(&env) {
+            Self::append_audit(&env, symbol_short!("repair"), &caller, false);
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT"), &Self::config_split(&env, &config));
 
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("repair"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        Self::record_event(&env, symbol_short!("repair"));
         Ok(true)
     }
 
+    /// True if the stored `SPLIT` vector matches the percentages in `CONFIG`.
+    /// For a dynamic split only the first four categories are mirrored in `CONFIG`.
+    /// An uninitialized contract has nothing to compare and reports true.
+    pub fn verify_integrity(env: Env) -> bool {
+        let config: Option<SplitConfig> = env.storage().instance().get(&symbol_short!("CONFIG"));
+        match config {
+            Some(config) => {
+                let expected = Self::config_split(&env, &config);
+                let stored: Option<Vec<u32>> =
+                    env.storage().instance().get(&symbol_short!("SPLIT"));
+                match stored {
+                    Some(split) if split.len() != 4 => {
+                        (0..4).all(|i| split.get(i).unwrap_or(0) == expected.get(i).unwrap_or(0))
+                    }
+                    stored => stored == Some(expected),
+                }
+            }
+            None => true,
+        }
+    }
+
+    fn config_split(env: &Env, config: &SplitConfig) -> Vec<u32> {
+        vec![
+            env,
+            config.spending_percent,
+            config.savings_percent,
+            config.bills_percent,
+            config.insurance_percent,
+        ]
+    }
+
     pub fn get_split(env: &Env) -> Vec<u32> {
         env.storage()
             .instance()
@@ -422,47 +1118,272 @@ impl RemittanceSplit {
             .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
     }
 
+    /// Split allocations in basis points (10000 = 100%), with the same default as `get_split`.
+    pub fn get_split_bps(env: &Env) -> Vec<u32> {
+        if Self::is_bps_mode(env.clone()) {
+            return Self::get_split(env);
+        }
+        let mut bps = Vec::new(env);
+        for percent in Self::get_split(env).iter() {
+            bps.push_back(percent * 100);
+        }
+        bps
+    }
+
+    /// Set the dust threshold below which `distribute_usdc` folds a bucket into
+    /// spending rather than transferring it (config owner only; 0 disables it).
+    pub fn set_min_transfer(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        min_transfer: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
+
+        let mut config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("min_xfer"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if min_transfer < 0 {
+            Self::append_audit(&env, symbol_short!("min_xfer"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        config.min_transfer = min_transfer;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONFIG"), &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("min_xfer"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        Self::record_event(&env, symbol_short!("min_xfer"));
+        Ok(true)
+    }
+
+    /// Allow (or forbid again) account groups that repeat an address, which
+    /// `distribute_usdc` and `preview_distribution` otherwise reject (config owner only).
+    pub fn set_allow_duplicate_recipients(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        allowed: bool,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
+
+        let mut config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("dup_recip"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        config.allow_duplicate_recipients = allowed;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONFIG"), &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("dup_recip"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        Self::record_event(&env, symbol_short!("dup_recip"));
+        Ok(true)
+    }
+
+    /// Lock the recipients in: once set, every distribution entry point
+    /// (`distribute_usdc`, `distribute_multi`, `distribute_once` and scheduled
+    /// distributions) rejects any other account group with `RecipientsLocked`, and
+    /// `distribute_usdc_stored` pays these without the caller naming them. Config
+    /// owner only.
+    pub fn set_accounts(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+        Self::require_writable(&env)?;
+
+        Self::require_owner(&env, &owner, symbol_short!("set_accts"))?;
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ACCOUNTS"), &accounts);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("set_accts"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), owner);
+        Self::record_event(&env, symbol_short!("set_accts"));
+        Ok(true)
+    }
+
+    /// Recipients locked in by `set_accounts`, if any
+    pub fn get_accounts(env: Env) -> Option<AccountGroup> {
+        env.storage().instance().get(&symbol_short!("ACCOUNTS"))
+    }
+
+    /// False when the owner locked in recipients with `set_accounts` and `accounts`
+    /// differs from them
+    fn recipients_allowed(env: &Env, accounts: &AccountGroup) -> bool {
+        Self::get_accounts(env.clone()).is_none_or(|locked| locked == *accounts)
+    }
+
+    /// Delete the split config so `initialize_split` can be called again, e.g. after
+    /// initializing with the wrong owner. Clears the config, the split, its
+    /// category/basis-point settings and the locked accounts; nonces, the audit log,
+    /// config history and distribution history are kept.
+    ///
+    /// # Panics
+    /// - If `owner` doesn't authorize the transaction
+    /// - If the split is not initialized or `owner` is not its owner
+    pub fn reset_config(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+        Self::require_writable(&env)?;
+        Self::require_owner(&env, &owner, symbol_short!("rst_cfg"))?;
+
+        Self::extend_instance_ttl(&env);
+        let storage = env.storage().instance();
+        for key in [
+            symbol_short!("CONFIG"),
+            symbol_short!("SPLIT"),
+            symbol_short!("CAT_NAMES"),
+            symbol_short!("BPS_MODE"),
+            symbol_short!("ACCOUNTS"),
+        ] {
+            storage.remove(&key);
+        }
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("rst_cfg"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::ConfigReset), owner);
+        Self::record_event(&env, symbol_short!("rst_cfg"));
+        Ok(true)
+    }
+
+    /// Hand the split config to `new_owner`. Only `current_owner`'s nonce is consumed;
+    /// `new_owner` keeps whatever `get_nonce` already reports for them.
+    ///
+    /// # Panics
+    /// - If `current_owner` doesn't authorize the transaction
+    /// - If the split is not initialized or `current_owner` is not its owner
+    pub fn transfer_ownership(
+        env: Env,
+        current_owner: Address,
+        nonce: u64,
+        new_owner: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        current_owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &current_owner, nonce)?;
+        Self::require_writable(&env)?;
+
+        let mut config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != current_owner {
+            Self::append_audit(&env, symbol_short!("xferown"), &current_owner, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        config.owner = new_owner.clone();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONFIG"), &config);
+
+        Self::increment_nonce(&env, &current_owner)?;
+        Self::append_audit(&env, symbol_short!("xferown"), &current_owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::OwnershipTransferred),
+            (current_owner, new_owner),
+        );
+        Self::record_event(&env, symbol_short!("xferown"));
+        Ok(true)
+    }
+
+    /// True once the split is stored in basis points rather than whole percentages
+    pub fn is_bps_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("BPS_MODE"))
+            .unwrap_or(false)
+    }
+
+    fn split_denominator(env: &Env) -> u32 {
+        if Self::is_bps_mode(env.clone()) {
+            BPS_DENOMINATOR
+        } else {
+            PERCENT_DENOMINATOR
+        }
+    }
+
     pub fn get_config(env: Env) -> Option<SplitConfig> {
         env.storage().instance().get(&symbol_short!("CONFIG"))
     }
 
+    /// Seconds between the config's creation (`SplitConfig::timestamp`) and `now_ts`;
+    /// 0 if the split is not initialized.
+    pub fn get_config_age(env: Env, now_ts: u64) -> u64 {
+        match Self::get_config(env) {
+            Some(config) => now_ts.saturating_sub(config.timestamp),
+            None => 0,
+        }
+    }
+
+    /// True when the config is at least `threshold_secs` old at `now_ts`.
+    pub fn is_config_stale(env: Env, now_ts: u64, threshold_secs: u64) -> bool {
+        match Self::get_config(env) {
+            Some(config) => now_ts.saturating_sub(config.timestamp) >= threshold_secs,
+            None => false,
+        }
+    }
+
     pub fn calculate_split(
         env: Env,
         total_amount: i128,
     ) -> Result<Vec<i128>, RemittanceSplitError> {
-        if total_amount <= 0 {
-            return Err(RemittanceSplitError::InvalidAmount);
-        }
-
-        let split = Self::get_split(&env);
-        let s0 = split.get(0).unwrap() as i128;
-        let s1 = split.get(1).unwrap() as i128;
-        let s2 = split.get(2).unwrap() as i128;
-
-        let spending = total_amount
-            .checked_mul(s0)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let savings = total_amount
-            .checked_mul(s1)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let bills = total_amount
-            .checked_mul(s2)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let insurance = total_amount
-            .checked_sub(spending)
-            .and_then(|n| n.checked_sub(savings))
-            .and_then(|n| n.checked_sub(bills))
-            .ok_or(RemittanceSplitError::Overflow)?;
+        let amounts = Self::split_amounts(&env, total_amount)?;
+        Self::publish_calculated(&env, total_amount, &amounts);
+        Ok(amounts)
+    }
 
+    /// Emit the `Calculated` events for a split of `total_amount` into `amounts`
+    fn publish_calculated(env: &Env, total_amount: i128, amounts: &Vec<i128>) {
         let event = SplitCalculatedEvent {
             total_amount,
-            spending_amount: spending,
-            savings_amount: savings,
-            bills_amount: bills,
-            insurance_amount: insurance,
+            spending_amount: amounts.get(0).unwrap_or(0),
+            savings_amount: amounts.get(1).unwrap_or(0),
+            bills_amount: amounts.get(2).unwrap_or(0),
+            insurance_amount: amounts.get(3).unwrap_or(0),
             timestamp: env.ledger().timestamp(),
         };
         env.events().publish((SPLIT_CALCULATED,), event);
@@ -470,64 +1391,1496 @@ impl RemittanceSplit {
             (symbol_short!("split"), SplitEvent::Calculated),
             total_amount,
         );
-
-        Ok(vec![&env, spending, savings, bills, insurance])
+        Self::record_event(env, symbol_short!("calc"));
     }
 
-    pub fn distribute_usdc(
+    /// Where `distribute_usdc` would send `total_amount` under the current config, as
+    /// `(recipient, amount, balance after the transfer)` in spending, savings, bills,
+    /// insurance order, followed by the fee collector when a platform fee applies.
+    /// Amounts come from the same plan `distribute_usdc` executes: platform fee,
+    /// category minimum and dust folding included. Read-only: no auth, no transfers,
+    /// no events.
+    pub fn preview_distribution(
         env: Env,
         usdc_contract: Address,
-        from: Address,
-        nonce: u64,
         accounts: AccountGroup,
         total_amount: i128,
-    ) -> Result<bool, RemittanceSplitError> {
-        if total_amount <= 0 {
-            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
-            return Err(RemittanceSplitError::InvalidAmount);
-        }
-
-        from.require_auth();
-        Self::require_nonce(&env, &from, nonce)?;
-
-        let amounts = Self::calculate_split(env.clone(), total_amount)?;
-        let recipients = [
+    ) -> Result<Vec<(Address, i128, i128)>, RemittanceSplitError> {
+        Self::require_distinct_recipients(&env, &accounts);
+        let plan = Self::plan_distribution(&env, total_amount)?;
+        let token = TokenClient::new(&env, &usdc_contract);
+        let mut transfers = Vec::new(&env);
+        for (recipient, amount) in [
             accounts.spending,
             accounts.savings,
             accounts.bills,
             accounts.insurance,
+        ]
+        .into_iter()
+        .zip(plan.amounts.iter())
+        {
+            transfers.push_back((recipient, amount));
+        }
+        if let Some(fee) = plan.fee {
+            transfers.push_back(fee);
+        }
+
+        // A recipient listed twice sees both amounts land on the same balance
+        let mut preview: Vec<(Address, i128, i128)> = Vec::new(&env);
+        for (recipient, amount) in transfers.iter() {
+            let before = match preview.iter().rev().find(|(r, _, _)| *r == recipient) {
+                Some((_, _, projected)) => projected,
+                None => token.balance(&recipient),
+            };
+            let projected = before
+                .checked_add(amount)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            preview.push_back((recipient, amount, projected));
+        }
+        Ok(preview)
+    }
+
+    /// Preview the split of `total_amount` under an arbitrary set of percentages.
+    ///
+    /// Uses the same rounding as `calculate_split` (insurance absorbs the remainder)
+    /// but never touches storage and emits no events, so it can be called before
+    /// the contract is initialized.
+    ///
+    /// # Returns
+    /// Vector [spending, savings, bills, insurance] amounts
+    pub fn preview_split_with(
+        env: Env,
+        total_amount: i128,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let total = spending_percent as u64
+            + savings_percent as u64
+            + bills_percent as u64
+            + insurance_percent as u64;
+        if total != PERCENT_DENOMINATOR as u64 {
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+        let split = vec![
+            &env,
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
         ];
-        let token = TokenClient::new(&env, &usdc_contract);
+        Self::compute_split_amounts(&env, total_amount, &split, PERCENT_DENOMINATOR)
+    }
 
-        for (amount, recipient) in amounts.into_iter().zip(recipients.iter()) {
-            if amount > 0 {
-                token.transfer(&from, recipient, &amount);
+    /// Split `total_amount` in proportion to integer `weights` (e.g. [2, 1, 1]),
+    /// which need not sum to 100. Each bucket is rounded down and the remainder goes
+    /// to the largest-weight bucket (the first one on ties), so the amounts always
+    /// sum to `total_amount`. Pure; reads no storage and emits no events.
+    ///
+    /// # Returns
+    /// One amount per weight, in the same order
+    pub fn calculate_split_by_weights(
+        env: Env,
+        total_amount: i128,
+        weights: Vec<u32>,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let weight_sum: i128 = weights.iter().map(|w| w as i128).sum();
+        if weight_sum == 0 {
+            return Err(RemittanceSplitError::InvalidWeights);
+        }
+
+        let mut amounts = Vec::new(&env);
+        let mut allocated: i128 = 0;
+        let mut largest = 0u32;
+        for (i, weight) in weights.iter().enumerate() {
+            if weight > weights.get(largest).unwrap_or(0) {
+                largest = i as u32;
             }
+            let amount = total_amount
+                .checked_mul(weight as i128)
+                .map(|n| n / weight_sum)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            allocated += amount;
+            amounts.push_back(amount);
+        }
+        let remainder = total_amount - allocated;
+        amounts.set(largest, amounts.get(largest).unwrap_or(0) + remainder);
+        Ok(amounts)
+    }
+
+    /// Transfer `total_amount` from `from` to the four category accounts.
+    ///
+    /// Once a split is configured, `from` must be the config owner or an operator
+    /// granted with `grant_operator`.
+    ///
+    /// Each recipient with a nonzero amount is pre-checked with `can_receive`; if one
+    /// fails, nothing moves, the nonce is not consumed, a failed audit entry whose
+    /// operation is the category (e.g. `BILLS`) is recorded, and `None` is returned.
+    /// A non-positive `total_amount` also returns `None` without moving anything.
+    ///
+    /// Panics with "Duplicate recipient address" if two categories share an address,
+    /// unless the owner allowed it with `set_allow_duplicate_recipients`.
+    ///
+    /// Rejects with `NotInitialized` when `initialize_split` was never called, unless
+    /// `allow_default` is set, in which case the built-in 50/30/15/5 split is used.
+    ///
+    /// # Returns
+    /// A `DistributionReceipt` of the transfers made
+    pub fn distribute_usdc(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+        allow_default: bool,
+    ) -> Result<Option<DistributionReceipt>, RemittanceSplitError> {
+        from.require_auth();
+        if total_amount <= 0 {
+            Self::require_distinct_recipients(&env, &accounts);
+            Self::append_audit_with_amount(
+                &env,
+                symbol_short!("distrib"),
+                &from,
+                false,
+                total_amount,
+            );
+            return Ok(None);
+        }
+
+        Self::require_nonce(&env, &from, nonce)?;
+        let check = if allow_default {
+            Ok(())
+        } else {
+            Self::require_initialized(&env)
+        }
+        .and_then(|_| match Self::get_config(env.clone()) {
+            Some(config)
+                if config.owner != from && !Self::is_operator(env.clone(), from.clone()) =>
+            {
+                Err(RemittanceSplitError::Unauthorized)
+            }
+            _ => Ok(()),
+        });
+        if let Err(e) = check {
+            Self::append_audit_with_amount(
+                &env,
+                symbol_short!("distrib"),
+                &from,
+                false,
+                total_amount,
+            );
+            return Err(e);
+        }
+
+        let receipt = Self::run_distribution(
+            &env,
+            &usdc_contract,
+            &from,
+            accounts,
+            total_amount,
+            symbol_short!("distrib"),
+        )?;
+        if receipt.is_some() {
+            Self::increment_nonce(&env, &from)?;
+        }
+        Ok(receipt)
+    }
+
+    /// The distribution pipeline shared by `distribute_usdc` and `execute_scheduled`,
+    /// run once the caller is authorized: distinct and locked recipients, pause,
+    /// read-only cutoff, freeze window and daily cap checks, then the
+    /// `plan_distribution` transfers, volume, audit entry and events. Failed checks
+    /// leave a failed `operation` audit entry; a breached daily cap or a recipient
+    /// that can't receive returns `None` with nothing moved.
+    fn run_distribution(
+        env: &Env,
+        usdc_contract: &Address,
+        from: &Address,
+        accounts: AccountGroup,
+        total_amount: i128,
+        operation: Symbol,
+    ) -> Result<Option<DistributionReceipt>, RemittanceSplitError> {
+        Self::require_distinct_recipients(env, &accounts);
+        let check = Self::require_not_paused(env)
+            .and_then(|_| Self::require_writable(env))
+            .and_then(|_| {
+                if !Self::recipients_allowed(env, &accounts) {
+                    Err(RemittanceSplitError::RecipientsLocked)
+                } else if Self::is_frozen(env) {
+                    Err(RemittanceSplitError::DistributionsFrozen)
+                } else if Self::is_distributions_paused(env.clone()) {
+                    Err(RemittanceSplitError::DistributionsPaused)
+                } else {
+                    Ok(())
+                }
+            })
+            .and_then(|_| Self::plan_distribution(env, total_amount));
+        let plan = match check {
+            Ok(plan) => plan,
+            Err(e) => {
+                Self::append_audit_with_amount(env, operation, from, false, total_amount);
+                return Err(e);
+            }
+        };
+        if !Self::within_daily_cap(env, total_amount) {
+            Self::append_audit_with_amount(env, operation, from, false, total_amount);
+            return Ok(None);
+        }
+
+        let fee = plan.fee.as_ref().map_or(0, |(_, fee)| *fee);
+        let net_amount = total_amount - fee - plan.overflow;
+        Self::publish_calculated(env, net_amount, &plan.amounts);
+        if !Self::transfer_split(
+            env,
+            usdc_contract,
+            from,
+            accounts,
+            net_amount,
+            plan.amounts.clone(),
+            plan.fee,
+        )? {
+            return Ok(None);
+        }
+        let mut allocations = Vec::new(env);
+        for (category, amount) in Self::split_categories()
+            .into_iter()
+            .zip(plan.amounts.iter())
+        {
+            allocations.push_back(Allocation { category, amount });
+        }
+        if fee > 0 {
+            allocations.push_back(Allocation {
+                category: symbol_short!("FEE"),
+                amount: fee,
+            });
+        }
+        if plan.overflow > 0 {
+            allocations.push_back(Allocation {
+                category: symbol_short!("OVERFLOW"),
+                amount: plan.overflow,
+            });
+        }
+
+        Self::record_volume(env, total_amount - plan.overflow)?;
+        Self::record_capped_volume(env, total_amount - plan.overflow)?;
+        Self::append_audit_with_amount(env, operation.clone(), from, true, total_amount);
+        env.events().publish(
+            (symbol_short!("split"), operation.clone()),
+            (fee, net_amount),
+        );
+        Self::record_event(env, operation);
+        Ok(Some(DistributionReceipt {
+            total_amount,
+            allocations,
+            token: usdc_contract.clone(),
+            timestamp: env.ledger().timestamp(),
+        }))
+    }
+
+    /// Split several token amounts in one call: each `(token_contract, total_amount)`
+    /// in `transfers` goes through the configured split exactly as `distribute_usdc`
+    /// would (platform fee, category minimum, dust threshold and caps included), emitting
+    /// one `Calculated` event per token. `from` authorizes once and the nonce is
+    /// consumed once. If any recipient can't receive, the whole call fails with
+    /// `RecipientCannotReceive` and no token moves.
+    pub fn distribute_multi(
+        env: Env,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        transfers: Vec<(Address, i128)>,
+    ) -> Result<bool, RemittanceSplitError> {
+        from.require_auth();
+        Self::require_nonce(&env, &from, nonce)?;
+
+        let grand_total = transfers
+            .iter()
+            .try_fold(0i128, |sum, (_, amount)| sum.checked_add(amount));
+        let check = if transfers.is_empty() || transfers.iter().any(|(_, amount)| amount <= 0) {
+            Err(RemittanceSplitError::InvalidAmount)
+        } else if grand_total.is_none() {
+            Err(RemittanceSplitError::Overflow)
+        } else if !Self::recipients_allowed(&env, &accounts) {
+            Err(RemittanceSplitError::RecipientsLocked)
+        } else if Self::is_frozen(&env) {
+            Err(RemittanceSplitError::DistributionsFrozen)
+        } else if Self::is_distributions_paused(env.clone()) {
+            Err(RemittanceSplitError::DistributionsPaused)
+        } else if !Self::within_daily_cap(&env, grand_total.unwrap_or(0)) {
+            Err(RemittanceSplitError::DailyCapExceeded)
+        } else {
+            Self::require_initialized(&env)
+                .and_then(|_| Self::require_not_paused(&env))
+                .and_then(|_| Self::require_writable(&env))
+        };
+        let grand_total = grand_total.unwrap_or(0);
+        if let Err(e) = check {
+            Self::append_audit_with_amount(
+                &env,
+                symbol_short!("dist_mult"),
+                &from,
+                false,
+                grand_total,
+            );
+            return Err(e);
+        }
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != from && !Self::is_operator(env.clone(), from.clone()) {
+            Self::append_audit_with_amount(
+                &env,
+                symbol_short!("dist_mult"),
+                &from,
+                false,
+                grand_total,
+            );
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        for (token, total_amount) in transfers.iter() {
+            let plan = Self::plan_distribution(&env, total_amount)?;
+            let fee = plan.fee.as_ref().map_or(0, |(_, fee)| *fee);
+            let net_amount = total_amount - fee - plan.overflow;
+            Self::publish_calculated(&env, net_amount, &plan.amounts);
+            if !Self::transfer_split(
+                &env,
+                &token,
+                &from,
+                accounts.clone(),
+                net_amount,
+                plan.amounts,
+                plan.fee,
+            )? {
+                return Err(RemittanceSplitError::RecipientCannotReceive);
+            }
+            Self::record_volume(&env, total_amount - plan.overflow)?;
+            Self::record_capped_volume(&env, total_amount - plan.overflow)?;
+        }
+
+        Self::increment_nonce(&env, &from)?;
+        Self::append_audit_with_amount(&env, symbol_short!("dist_mult"), &from, true, grand_total);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("dist_mult")),
+            (from, transfers.len()),
+        );
+        Self::record_event(&env, symbol_short!("dist_mult"));
+        Ok(true)
+    }
+
+    /// One-off distribution with an inline split: `spending`/`savings`/`bills`/`insurance`
+    /// must sum to 100. The stored split is neither read nor written, so no owner or
+    /// operator check applies; the nonce, recipients locked with `set_accounts`, freeze
+    /// window, distribution pause, read-only cutoff and recipient pre-check still do.
+    /// The daily cap applies only when `from` is the config owner or an operator.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribute_once(
+        env: Env,
+        from: Address,
+        nonce: u64,
+        usdc_contract: Address,
+        accounts: AccountGroup,
+        total_amount: i128,
+        spending: u32,
+        savings: u32,
+        bills: u32,
+        insurance: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        from.require_auth();
+        Self::require_nonce(&env, &from, nonce)?;
+
+        let capped = Self::counts_toward_cap(&env, &from);
+        let check = if total_amount <= 0 {
+            Err(RemittanceSplitError::InvalidAmount)
+        } else if [spending, savings, bills, insurance]
+            .iter()
+            .map(|p| *p as u64)
+            .sum::<u64>()
+            != 100
+        {
+            Err(RemittanceSplitError::PercentagesDoNotSumTo100)
+        } else if !Self::recipients_allowed(&env, &accounts) {
+            Err(RemittanceSplitError::RecipientsLocked)
+        } else if Self::is_frozen(&env) {
+            Err(RemittanceSplitError::DistributionsFrozen)
+        } else if Self::is_distributions_paused(env.clone()) {
+            Err(RemittanceSplitError::DistributionsPaused)
+        } else if capped && !Self::within_daily_cap(&env, total_amount) {
+            Err(RemittanceSplitError::DailyCapExceeded)
+        } else {
+            Self::require_not_paused(&env).and_then(|_| Self::require_writable(&env))
+        };
+        if let Err(e) = check {
+            Self::append_audit_with_amount(
+                &env,
+                symbol_short!("dist_once"),
+                &from,
+                false,
+                total_amount,
+            );
+            return Err(e);
+        }
+
+        let split = vec![&env, spending, savings, bills, insurance];
+        let amounts = Self::compute_split_amounts(&env, total_amount, &split, PERCENT_DENOMINATOR)?;
+        if !Self::transfer_split(
+            &env,
+            &usdc_contract,
+            &from,
+            accounts,
+            total_amount,
+            amounts,
+            None,
+        )? {
+            return Ok(false);
+        }
+
+        Self::increment_nonce(&env, &from)?;
+        Self::record_volume(&env, total_amount)?;
+        if capped {
+            Self::record_capped_volume(&env, total_amount)?;
+        }
+        Self::append_audit_with_amount(&env, symbol_short!("dist_once"), &from, true, total_amount);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("dist_once")),
+            (from, total_amount),
+        );
+        Self::record_event(&env, symbol_short!("dist_once"));
+        Ok(true)
+    }
+
+    /// Pre-check every recipient and the fee collector, then transfer `amounts` and
+    /// `fee` from `from` and record the distribution and each recipient's running
+    /// total. Returns false, with nothing moved, if a recipient can't receive.
+    fn transfer_split(
+        env: &Env,
+        usdc_contract: &Address,
+        from: &Address,
+        accounts: AccountGroup,
+        total_amount: i128,
+        amounts: Vec<i128>,
+        fee: Option<FeeTransfer>,
+    ) -> Result<bool, RemittanceSplitError> {
+        let recipients = [
+            accounts.spending,
+            accounts.savings,
+            accounts.bills,
+            accounts.insurance,
+        ];
+        let token = TokenClient::new(env, usdc_contract);
+
+        for ((category, amount), recipient) in Self::split_categories()
+            .into_iter()
+            .zip(amounts.iter())
+            .zip(recipients.iter())
+        {
+            if amount > 0 && !Self::can_receive(env, usdc_contract, recipient) {
+                // The audit operation names the category that cannot receive
+                Self::append_audit_with_amount(env, category, from, false, amount);
+                return Ok(false);
+            }
+        }
+        if let Some((collector, fee)) = &fee {
+            if !Self::can_receive(env, usdc_contract, collector) {
+                Self::append_audit_with_amount(env, symbol_short!("FEE"), from, false, *fee);
+                return Ok(false);
+            }
+        }
+
+        let storage = env.storage().persistent();
+        for (amount, recipient) in amounts.clone().into_iter().zip(recipients.iter()) {
+            if amount > 0 {
+                let key = Self::received_key(env, recipient);
+                let total = storage
+                    .get::<_, i128>(&key)
+                    .unwrap_or(0)
+                    .checked_add(amount)
+                    .ok_or(RemittanceSplitError::Overflow)?;
+                storage.set(&key, &total);
+                storage.extend_ttl(&key, AUDIT_LIFETIME_THRESHOLD, AUDIT_BUMP_AMOUNT);
+                token.transfer(from, recipient, &amount);
+            }
+        }
+        if let Some((collector, fee)) = fee {
+            token.transfer(from, &collector, &fee);
+        }
+
+        let [spending_to, savings_to, bills_to, insurance_to] = recipients;
+        Self::append_distribution(
+            env,
+            DistributionRecord {
+                timestamp: env.ledger().timestamp(),
+                total: total_amount,
+                spending_to,
+                savings_to,
+                bills_to,
+                insurance_to,
+                amounts,
+            },
+        );
+        Ok(true)
+    }
+
+    /// Total ever transferred to `recipient` by `distribute_usdc` and `distribute_once`
+    pub fn get_received_total(env: Env, recipient: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::received_key(&env, &recipient))
+            .unwrap_or(0)
+    }
+
+    /// Persistent key of `recipient`'s received total. `reset` moves to a new epoch
+    /// instead of deleting every recipient's key; the old ones expire with their TTL.
+    fn received_key(env: &Env, recipient: &Address) -> (Symbol, u32, Address) {
+        let epoch: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("RCV_EPOCH"))
+            .unwrap_or(0);
+        (symbol_short!("RECEIVED"), epoch, recipient.clone())
+    }
+
+    /// Best-effort check that `recipient` can hold `token`.
+    ///
+    /// A failing `balance` read means the recipient has no trustline. For Stellar
+    /// asset contracts, a deauthorized balance is also rejected. Soroban cannot
+    /// pre-check everything: custom tokens may reject transfers for their own
+    /// reasons, and an issuer can change authorization before the transfer lands.
+    fn can_receive(env: &Env, token: &Address, recipient: &Address) -> bool {
+        if !matches!(
+            TokenClient::new(env, token).try_balance(recipient),
+            Ok(Ok(_))
+        ) {
+            return false;
+        }
+        // Tokens without `authorized` (non-SAC) are given the benefit of the doubt
+        !matches!(
+            StellarAssetClient::new(env, token).try_authorized(recipient),
+            Ok(Ok(false))
+        )
+    }
+
+    /// Let `operator` run distributions. Operators cannot change the split or any
+    /// other owner setting.
+    pub fn grant_operator(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        operator: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        Self::set_operator(&env, &owner, nonce, &operator, true)
+    }
+
+    pub fn revoke_operator(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        operator: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        Self::set_operator(&env, &owner, nonce, &operator, false)
+    }
+
+    pub fn is_operator(env: Env, address: Address) -> bool {
+        let operators: Option<Map<Address, bool>> =
+            env.storage().instance().get(&symbol_short!("OPERATORS"));
+        operators
+            .and_then(|operators| operators.get(address))
+            .unwrap_or(false)
+    }
+
+    fn set_operator(
+        env: &Env,
+        owner: &Address,
+        nonce: u64,
+        operator: &Address,
+        granted: bool,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_nonce(env, owner, nonce)?;
+        let operation = if granted {
+            symbol_short!("op_grant")
+        } else {
+            symbol_short!("op_revoke")
+        };
+        Self::require_owner(env, owner, operation.clone())?;
+
+        Self::extend_instance_ttl(env);
+        let mut operators: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("OPERATORS"))
+            .unwrap_or_else(|| Map::new(env));
+        if granted {
+            operators.set(operator.clone(), true);
+        } else {
+            operators.remove(operator.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("OPERATORS"), &operators);
+
+        Self::increment_nonce(env, owner)?;
+        Self::append_audit(env, operation.clone(), owner, true);
+        env.events().publish(
+            (symbol_short!("split"), operation.clone()),
+            operator.clone(),
+        );
+        Self::record_event(env, operation);
+        Ok(true)
+    }
+
+    /// Freeze distributions between `start_ts` (inclusive) and `end_ts` (exclusive).
+    /// Reads and config updates remain allowed while frozen.
+    pub fn set_freeze_window(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("freeze"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if end_ts <= start_ts {
+            Self::append_audit(&env, symbol_short!("freeze"), &caller, false);
+            return Err(RemittanceSplitError::InvalidFreezeWindow);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FREEZE"), &FreezeWindow { start_ts, end_ts });
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("freeze"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("frozen")),
+            (start_ts, end_ts),
+        );
+        Self::record_event(&env, symbol_short!("frozen"));
+        Ok(true)
+    }
+
+    pub fn clear_freeze_window(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("unfreeze"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().remove(&symbol_short!("FREEZE"));
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("unfreeze"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("unfrozen")), ());
+        Self::record_event(&env, symbol_short!("unfrozen"));
+        Ok(true)
+    }
+
+    pub fn get_freeze_window(env: Env) -> Option<FreezeWindow> {
+        env.storage().instance().get(&symbol_short!("FREEZE"))
+    }
+
+    /// Make the contract permanently read-only from `timestamp` on: `update_split`,
+    /// `distribute_usdc` and `import_snapshot` are rejected, reads keep working.
+    /// Used to sunset a deployment; it cannot be changed once the time has passed.
+    pub fn set_readonly_after(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        timestamp: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("readonly"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("RO_AFTER"), &timestamp);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("readonly"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("readonly")),
+            timestamp,
+        );
+        Self::record_event(&env, symbol_short!("readonly"));
+        Ok(true)
+    }
+
+    pub fn get_readonly_after(env: Env) -> Option<u64> {
+        env.storage().instance().get(&symbol_short!("RO_AFTER"))
+    }
+
+    fn require_writable(env: &Env) -> Result<(), RemittanceSplitError> {
+        let readonly_after: Option<u64> = env.storage().instance().get(&symbol_short!("RO_AFTER"));
+        match readonly_after {
+            Some(ts) if env.ledger().timestamp() >= ts => Err(RemittanceSplitError::ReadOnly),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_frozen(env: &Env) -> bool {
+        let window: Option<FreezeWindow> = env.storage().instance().get(&symbol_short!("FREEZE"));
+        match window {
+            Some(w) => {
+                let now = env.ledger().timestamp();
+                now >= w.start_ts && now < w.end_ts
+            }
+            None => false,
+        }
+    }
+
+    /// Total amount distributed on `day_index` (ledger timestamp / 86400).
+    pub fn get_daily_volume(env: Env, day_index: u64) -> i128 {
+        let volumes: Map<u64, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("VOL_DAY"))
+            .unwrap_or_else(|| Map::new(&env));
+        volumes.get(day_index).unwrap_or(0)
+    }
+
+    /// Daily volumes for `start_day..=end_day`, one entry per day (0 for days without
+    /// distributions). At most `MAX_VOLUME_BUCKETS` days are returned.
+    pub fn get_volume_range(env: Env, start_day: u64, end_day: u64) -> Vec<i128> {
+        let volumes: Map<u64, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("VOL_DAY"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        if end_day < start_day {
+            return result;
+        }
+        let last_day = end_day.min(start_day + MAX_VOLUME_BUCKETS as u64 - 1);
+        for day in start_day..=last_day {
+            result.push_back(volumes.get(day).unwrap_or(0));
+        }
+        result
+    }
+
+    /// Cap on the total every distribution path (`distribute_usdc`, `distribute_multi`,
+    /// scheduled runs and the owner's or operators' `distribute_once`) may move within
+    /// the last 24 hours (config owner only; 0 removes the cap). The window is only
+    /// tracked while a cap is set, so a new cap starts from an empty window.
+    pub fn set_daily_cap(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        daily_distribution_cap: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_owner(&env, &caller, symbol_short!("day_cap"))?;
+        if daily_distribution_cap < 0 {
+            Self::append_audit(&env, symbol_short!("day_cap"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DAY_CAP"), &daily_distribution_cap);
+        if daily_distribution_cap == 0 {
+            env.storage().instance().remove(&symbol_short!("DAY_LOG"));
+        }
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("day_cap"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_daily_cap(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("DAY_CAP"))
+            .unwrap_or(0)
+    }
+
+    /// Amount counted against the daily cap over the last `SECONDS_PER_DAY` seconds,
+    /// at hourly resolution: a distribution counts until a day after the end of its
+    /// hour. Always 0 while no cap is set.
+    pub fn get_distributed_today(env: Env) -> i128 {
+        Self::daily_window(&env)
+            .iter()
+            .fold(0i128, |sum, (_, amount)| sum.saturating_add(amount))
+    }
+
+    /// `(bucket_start, amount)` of each hourly bucket still inside the rolling window
+    fn daily_window(env: &Env) -> Vec<(u64, i128)> {
+        let now = env.ledger().timestamp();
+        let log: Vec<(u64, i128)> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("DAY_LOG"))
+            .unwrap_or_else(|| Vec::new(env));
+        let mut window = Vec::new(env);
+        for (bucket, amount) in log.iter() {
+            if bucket.saturating_add(DAY_LOG_BUCKET + SECONDS_PER_DAY) > now {
+                window.push_back((bucket, amount));
+            }
+        }
+        window
+    }
+
+    /// Whether `from`'s distributions count against the owner's daily cap: those of
+    /// the config owner and operators do, other senders' `distribute_once` calls don't
+    fn counts_toward_cap(env: &Env, from: &Address) -> bool {
+        Self::get_config(env.clone()).is_some_and(|config| {
+            config.owner == *from || Self::is_operator(env.clone(), from.clone())
+        })
+    }
+
+    /// Whether distributing `amount` more keeps the rolling window within the daily cap
+    fn within_daily_cap(env: &Env, amount: i128) -> bool {
+        let cap = Self::get_daily_cap(env.clone());
+        cap == 0 || Self::get_distributed_today(env.clone()).saturating_add(amount) <= cap
+    }
+
+    /// Add `amount` to the daily-cap window, if a cap is set
+    fn record_capped_volume(env: &Env, amount: i128) -> Result<(), RemittanceSplitError> {
+        if Self::get_daily_cap(env.clone()) == 0 {
+            return Ok(());
+        }
+        // Buckets that left the window are dropped; same-hour amounts are merged
+        let bucket = env.ledger().timestamp() / DAY_LOG_BUCKET * DAY_LOG_BUCKET;
+        let mut window = Self::daily_window(env);
+        match window.last() {
+            Some((last, distributed)) if last == bucket => {
+                let distributed = distributed
+                    .checked_add(amount)
+                    .ok_or(RemittanceSplitError::Overflow)?;
+                window.set(window.len() - 1, (bucket, distributed));
+            }
+            _ => window.push_back((bucket, amount)),
+        }
+        while window.len() > MAX_DAY_LOG_BUCKETS {
+            window.pop_front();
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DAY_LOG"), &window);
+        Ok(())
+    }
+
+    fn record_volume(env: &Env, amount: i128) -> Result<(), RemittanceSplitError> {
+        let mut volumes: Map<u64, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("VOL_DAY"))
+            .unwrap_or_else(|| Map::new(env));
+        let day = env.ledger().timestamp() / SECONDS_PER_DAY;
+        let total = volumes
+            .get(day)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(RemittanceSplitError::Overflow)?;
+        volumes.set(day, total);
+        while volumes.len() > MAX_VOLUME_BUCKETS {
+            if let Some(oldest) = volumes.keys().first() {
+                volumes.remove(oldest);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("VOL_DAY"), &volumes);
+        Ok(())
+    }
+
+    /// Check an AccountGroup before signing a distribution. Returns the categories whose
+    /// address is shared with another category or equals `from`; empty when all good.
+    pub fn validate_accounts(
+        env: Env,
+        accounts: AccountGroup,
+        from: Option<Address>,
+    ) -> Vec<Symbol> {
+        let entries = [
+            (symbol_short!("SPENDING"), accounts.spending),
+            (symbol_short!("SAVINGS"), accounts.savings),
+            (symbol_short!("BILLS"), accounts.bills),
+            (symbol_short!("INSURANCE"), accounts.insurance),
+        ];
+
+        let mut problems = Vec::new(&env);
+        for (i, (category, address)) in entries.iter().enumerate() {
+            let duplicated = entries
+                .iter()
+                .enumerate()
+                .any(|(j, (_, other))| i != j && other == address);
+            let is_sender = from.as_ref() == Some(address);
+            if duplicated || is_sender {
+                problems.push_back(category.clone());
+            }
+        }
+        problems
+    }
+
+    /// Register a friendly alias for a recipient address (config owner only).
+    pub fn set_alias(
+        env: Env,
+        owner: Address,
+        alias: Symbol,
+        address: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != owner {
+            Self::append_audit(&env, symbol_short!("alias"), &owner, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut aliases: Map<Symbol, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ALIASES"))
+            .unwrap_or_else(|| Map::new(&env));
+        aliases.set(alias, address);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ALIASES"), &aliases);
+
+        Self::append_audit(&env, symbol_short!("alias"), &owner, true);
+        Ok(true)
+    }
+
+    pub fn get_alias(env: Env, alias: Symbol) -> Option<Address> {
+        let aliases: Map<Symbol, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ALIASES"))
+            .unwrap_or_else(|| Map::new(&env));
+        aliases.get(alias)
+    }
+
+    /// Same as `distribute_usdc`, with each recipient given by a registered alias.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribute_usdc_by_alias(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        spending: Symbol,
+        savings: Symbol,
+        bills: Symbol,
+        insurance: Symbol,
+        total_amount: i128,
+    ) -> Result<Option<DistributionReceipt>, RemittanceSplitError> {
+        let resolve = |alias: Symbol| -> Result<Address, RemittanceSplitError> {
+            Self::get_alias(env.clone(), alias).ok_or(RemittanceSplitError::AliasNotFound)
+        };
+        let accounts = AccountGroup {
+            spending: resolve(spending)?,
+            savings: resolve(savings)?,
+            bills: resolve(bills)?,
+            insurance: resolve(insurance)?,
+        };
+        Self::distribute_usdc(
+            env,
+            usdc_contract,
+            from,
+            nonce,
+            accounts,
+            total_amount,
+            false,
+        )
+    }
+
+    /// Same as `distribute_usdc`, paying the recipients stored with `set_accounts`.
+    pub fn distribute_usdc_stored(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        total_amount: i128,
+    ) -> Result<Option<DistributionReceipt>, RemittanceSplitError> {
+        Self::require_initialized(&env)?;
+        let accounts =
+            Self::get_accounts(env.clone()).ok_or(RemittanceSplitError::AccountsNotSet)?;
+        Self::distribute_usdc(
+            env,
+            usdc_contract,
+            from,
+            nonce,
+            accounts,
+            total_amount,
+            false,
+        )
+    }
+
+    /// Categories that would receive nothing if `total_amount` were distributed
+    /// under the current split. Pure read: no events, no storage writes.
+    ///
+    /// Non-positive totals report every category; an amount too large to split
+    /// reports none.
+    pub fn get_skipped_categories(env: Env, total_amount: i128) -> Vec<Symbol> {
+        let categories = [
+            symbol_short!("SPENDING"),
+            symbol_short!("SAVINGS"),
+            symbol_short!("BILLS"),
+            symbol_short!("INSURANCE"),
+        ];
+        let mut skipped = Vec::new(&env);
+        if total_amount <= 0 {
+            for category in categories {
+                skipped.push_back(category);
+            }
+            return skipped;
+        }
+
+        let split = Self::get_split(&env);
+        let denominator = Self::split_denominator(&env);
+        let amounts = match Self::compute_split_amounts(&env, total_amount, &split, denominator) {
+            Ok(amounts) => amounts,
+            Err(_) => return skipped,
+        };
+        for (category, amount) in categories.into_iter().zip(amounts.iter()) {
+            if amount == 0 {
+                skipped.push_back(category);
+            }
+        }
+        skipped
+    }
+
+    /// Number of token transfers `distribute_usdc` would perform for
+    /// `total_amount` under the current config: one per nonzero bucket after dust
+    /// folding, plus the platform fee transfer when one is taken. Lets clients
+    /// budget fees before sending. Pure read; returns 0 if the amount cannot be
+    /// split.
+    pub fn estimate_transfers(env: Env, total_amount: i128) -> u32 {
+        match Self::plan_distribution(&env, total_amount) {
+            Ok(plan) => {
+                let fee_transfers = plan.fee.is_some() as u32;
+                plan.amounts.iter().filter(|amount| *amount > 0).count() as u32 + fee_transfers
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Net financial position of `owner`: total saved across goals, minus unpaid bills,
+    /// minus one cycle of active insurance premiums. Negative when obligations exceed
+    /// savings. An owner with nothing in a contract contributes 0 from it.
+    pub fn get_net_position(
+        env: Env,
+        owner: Address,
+        savings_addr: Address,
+        insurance_addr: Address,
+        bills_addr: Address,
+    ) -> i128 {
+        let saved = SavingsGoalsClient::new(&env, &savings_addr).get_total_saved(&owner);
+        let unpaid = BillPaymentsClient::new(&env, &bills_addr).get_total_unpaid(&owner);
+        let premiums =
+            InsuranceClient::new(&env, &insurance_addr).get_total_monthly_premium(&owner);
+        saved.saturating_sub(unpaid).saturating_sub(premiums)
+    }
+
+    pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
+        TokenClient::new(env, &usdc_contract).balance(&account)
+    }
+
+    pub fn get_split_allocations(
+        env: &Env,
+        total_amount: i128,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        let amounts = Self::calculate_split(env.clone(), total_amount)?;
+        let categories = [
+            symbol_short!("SPENDING"),
+            symbol_short!("SAVINGS"),
+            symbol_short!("BILLS"),
+            symbol_short!("INSURANCE"),
+        ];
+
+        let mut result = Vec::new(env);
+        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
+            result.push_back(Allocation { category, amount });
+        }
+        Ok(result)
+    }
+
+    /// Smallest amount `distribute_usdc` sends to each category with a nonzero
+    /// percentage (config owner only; 0 disables). Shortfalls are borrowed from the
+    /// largest allocations, so the total is unchanged.
+    pub fn set_min_category_amount(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        min_category_amount: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_owner(&env, &caller, symbol_short!("set_min"))?;
+        if min_category_amount < 0 {
+            Self::append_audit(&env, symbol_short!("set_min"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MIN_CAT"), &min_category_amount);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("set_min"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_min_category_amount(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("MIN_CAT"))
+            .unwrap_or(0)
+    }
+
+    /// Raise every nonzero-percentage category to the configured minimum, borrowing
+    /// from the largest allocations without taking any of them below the minimum.
+    /// Fails with `BelowCategoryMinimum` when the total can't cover every minimum.
+    fn apply_category_minimum(
+        env: &Env,
+        mut amounts: Vec<i128>,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let min = Self::get_min_category_amount(env.clone());
+        if min == 0 {
+            return Ok(amounts);
+        }
+
+        let split = Self::get_split(env);
+        let funded: u32 = split.iter().filter(|percent| *percent > 0).count() as u32;
+        let total: i128 = amounts.iter().sum();
+        if total < min.saturating_mul(funded as i128) {
+            return Err(RemittanceSplitError::BelowCategoryMinimum);
+        }
+
+        for i in 0..amounts.len() {
+            if split.get(i).unwrap_or(0) == 0 {
+                continue;
+            }
+            let mut need = min - amounts.get(i).unwrap_or(0);
+            while need > 0 {
+                let mut donor = i;
+                let mut donor_amount = min;
+                for (j, amount) in amounts.iter().enumerate() {
+                    if amount > donor_amount {
+                        donor = j as u32;
+                        donor_amount = amount;
+                    }
+                }
+                if donor == i {
+                    return Err(RemittanceSplitError::BelowCategoryMinimum);
+                }
+                let take = need.min(donor_amount - min);
+                amounts.set(donor, donor_amount - take);
+                amounts.set(i, amounts.get(i).unwrap_or(0) + take);
+                need -= take;
+            }
+        }
+        Ok(amounts)
+    }
+
+    /// Platform fee on `total_amount`; zero when no collector is configured
+    fn platform_fee(
+        fee_bps: u32,
+        fee_collector: &Option<Address>,
+        total_amount: i128,
+    ) -> Result<i128, RemittanceSplitError> {
+        match fee_collector {
+            Some(_) => total_amount
+                .checked_mul(fee_bps as i128)
+                .map(|n| n / 10_000)
+                .ok_or(RemittanceSplitError::Overflow),
+            None => Ok(0),
+        }
+    }
+
+    /// The platform fee transfer (collector and amount, only when nonzero) and the four
+    /// category amounts a distribution of `total_amount` moves: the fee comes off
+    /// first, the rest is split, raised to the category minimum, stripped of dust and
+    /// finally cut to the category caps. Every distribution path and preview uses
+    /// this, so what is previewed is what is transferred. No events.
+    fn plan_distribution(
+        env: &Env,
+        total_amount: i128,
+    ) -> Result<DistributionPlan, RemittanceSplitError> {
+        let (fee_bps, fee_collector, min_transfer) = match env
+            .storage()
+            .instance()
+            .get::<_, SplitConfig>(&symbol_short!("CONFIG"))
+        {
+            Some(config) => (config.fee_bps, config.fee_collector, config.min_transfer),
+            None => (0, None, 0),
+        };
+        let fee = Self::platform_fee(fee_bps, &fee_collector, total_amount)?;
+        let amounts = Self::split_amounts(env, total_amount - fee)?;
+        let amounts = Self::apply_category_minimum(env, amounts)?;
+        let amounts = Self::fold_dust(amounts, min_transfer);
+        let (amounts, overflow) = Self::apply_category_caps(env, amounts);
+        Ok(DistributionPlan {
+            fee: fee_collector
+                .filter(|_| fee > 0)
+                .map(|collector| (collector, fee)),
+            amounts,
+            overflow,
+        })
+    }
+
+    /// Cut capped categories to their cap and move the excess to the first uncapped
+    /// category in split order. Returns the excess left over when all four are capped.
+    fn apply_category_caps(env: &Env, mut amounts: Vec<i128>) -> (Vec<i128>, i128) {
+        let caps: Map<Symbol, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_CAPS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut overflow = 0i128;
+        let mut sink = None;
+        for (i, category) in Self::split_categories().iter().enumerate() {
+            let amount = amounts.get(i as u32).unwrap_or(0);
+            match caps.get(category.clone()) {
+                Some(cap) if amount > cap => {
+                    overflow += amount - cap;
+                    amounts.set(i as u32, cap);
+                }
+                Some(_) => {}
+                None => {
+                    if sink.is_none() {
+                        sink = Some(i as u32);
+                    }
+                }
+            }
+        }
+        if overflow > 0 {
+            if let Some(i) = sink {
+                amounts.set(i, amounts.get(i).unwrap_or(0) + overflow);
+                overflow = 0;
+            }
+        }
+        (amounts, overflow)
+    }
+
+    /// Panic if two categories of `accounts` share an address, unless the owner
+    /// opted in with `set_allow_duplicate_recipients`.
+    fn require_distinct_recipients(env: &Env, accounts: &AccountGroup) {
+        let allowed = env
+            .storage()
+            .instance()
+            .get::<_, SplitConfig>(&symbol_short!("CONFIG"))
+            .map(|config| config.allow_duplicate_recipients)
+            .unwrap_or(false);
+        if allowed {
+            return;
+        }
+        let recipients = [
+            &accounts.spending,
+            &accounts.savings,
+            &accounts.bills,
+            &accounts.insurance,
+        ];
+        for (i, recipient) in recipients.iter().enumerate() {
+            if recipients[i + 1..].contains(recipient) {
+                panic!("Duplicate recipient address");
+            }
+        }
+    }
+
+    /// Move every non-spending amount below `min_transfer` into spending, so the
+    /// dust is never transferred on its own and the total is unchanged.
+    fn fold_dust(mut amounts: Vec<i128>, min_transfer: i128) -> Vec<i128> {
+        let mut folded = 0i128;
+        for i in 1..amounts.len() {
+            let amount = amounts.get(i).unwrap_or(0);
+            if amount > 0 && amount < min_transfer {
+                folded += amount;
+                amounts.set(i, 0);
+            }
+        }
+        if folded > 0 {
+            amounts.set(0, amounts.get(0).unwrap_or(0) + folded);
+        }
+        amounts
+    }
+
+    /// Cap the amount one distribution may route to `category` (config owner only).
+    /// A cap of 0 removes it.
+    pub fn set_category_cap(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        category: Symbol,
+        cap: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_owner(&env, &caller, symbol_short!("set_cap"))?;
+        if !Self::split_categories().contains(&category) {
+            Self::append_audit(&env, symbol_short!("set_cap"), &caller, false);
+            return Err(RemittanceSplitError::InvalidCategory);
+        }
+        if cap < 0 {
+            Self::append_audit(&env, symbol_short!("set_cap"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut caps: Map<Symbol, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_CAPS"))
+            .unwrap_or_else(|| Map::new(&env));
+        if cap == 0 {
+            caps.remove(category);
+        } else {
+            caps.set(category, cap);
         }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_CAPS"), &caps);
 
-        Self::increment_nonce(&env, &from)?;
-        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("set_cap"), &caller, true);
         Ok(true)
     }
 
-    pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
-        TokenClient::new(env, &usdc_contract).balance(&account)
+    /// Per-distribution cap for `category` (0 when uncapped)
+    pub fn get_category_cap(env: Env, category: Symbol) -> i128 {
+        let caps: Option<Map<Symbol, i128>> =
+            env.storage().instance().get(&symbol_short!("CAT_CAPS"));
+        caps.and_then(|caps| caps.get(category)).unwrap_or(0)
     }
 
-    pub fn get_split_allocations(
-        env: &Env,
-        total_amount: i128,
-    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
-        let amounts = Self::calculate_split(env.clone(), total_amount)?;
-        let categories = [
+    /// Exactly what each recipient would get for `total_amount`, from the same plan
+    /// `distribute_usdc` executes: the platform fee is deducted first (reported as a
+    /// `FEE` pseudo-category), the remainder is split and rounded as in
+    /// `calculate_split`, raised to the category minimum, stripped of dust, then capped
+    /// categories are cut to their cap. Overflow goes to the first uncapped category in
+    /// split order, or to an `OVERFLOW` pseudo-category (kept by the sender) if all four
+    /// are capped. Pure read; empty if the amount cannot be split.
+    pub fn resolve_distribution(env: Env, total_amount: i128) -> Vec<Allocation> {
+        let mut result = Vec::new(&env);
+        let plan = match Self::plan_distribution(&env, total_amount) {
+            Ok(plan) => plan,
+            Err(_) => return result,
+        };
+
+        for (category, amount) in Self::split_categories()
+            .into_iter()
+            .zip(plan.amounts.iter())
+        {
+            result.push_back(Allocation { category, amount });
+        }
+        result.push_back(Allocation {
+            category: symbol_short!("FEE"),
+            amount: plan.fee.map_or(0, |(_, fee)| fee),
+        });
+        if plan.overflow > 0 {
+            result.push_back(Allocation {
+                category: symbol_short!("OVERFLOW"),
+                amount: plan.overflow,
+            });
+        }
+        result
+    }
+
+    fn split_categories() -> [Symbol; 4] {
+        [
             symbol_short!("SPENDING"),
             symbol_short!("SAVINGS"),
             symbol_short!("BILLS"),
             symbol_short!("INSURANCE"),
-        ];
+        ]
+    }
 
-        let mut result = Vec::new(env);
-        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
+    fn require_owner(
+        env: &Env,
+        caller: &Address,
+        operation: Symbol,
+    ) -> Result<(), RemittanceSplitError> {
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != *caller {
+            Self::append_audit(env, operation, caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Split allocations floored to multiples of `unit` (e.g. `10_000_000` for whole
+    /// dollars of a 7-decimal token). The rounding residue goes to insurance, the
+    /// remainder category, so the amounts still sum to `total_amount`; insurance is
+    /// only a whole multiple itself when `total_amount` is. Pure read: no events, no
+    /// storage writes.
+    pub fn get_split_allocations_rounded(
+        env: Env,
+        total_amount: i128,
+        unit: i128,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        if unit <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let amounts = Self::split_amounts(&env, total_amount)?;
+
+        let mut result = Vec::new(&env);
+        let mut allocated = 0i128;
+        let categories = Self::split_categories();
+        let last = categories.len() - 1;
+        for (i, (category, amount)) in categories.into_iter().zip(amounts.iter()).enumerate() {
+            let amount = if i == last {
+                total_amount - allocated
+            } else {
+                amount - amount % unit
+            };
+            allocated += amount;
             result.push_back(Allocation { category, amount });
         }
         Ok(result)
@@ -539,9 +2892,32 @@ impl RemittanceSplit {
         nonces.as_ref().and_then(|m| m.get(address)).unwrap_or(0)
     }
 
+    /// Nonce the next nonce-protected call from `address` must pass. Every nonce-taking
+    /// entry point shares this one counter, which advances by one after each successful call.
+    pub fn get_next_nonce(env: Env, address: Address) -> u64 {
+        Self::get_nonce(env, address)
+    }
+
+    /// True if `nonce` is the value the next nonce-protected call from `address` must
+    /// pass. Needs no auth and changes nothing.
+    pub fn is_nonce_valid(env: Env, address: Address, nonce: u64) -> bool {
+        Self::get_nonce(env, address) == nonce
+    }
+
     pub fn export_snapshot(
         env: Env,
         caller: Address,
+    ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
+        Self::export_snapshot_with_algo(env, caller, LATEST_CHECKSUM_ALGO)
+    }
+
+    /// Export the config using a specific checksum algorithm. Only `CHECKSUM_ALGO_FNV`
+    /// is exported; the legacy algorithms fail with `ChecksumOutdated`, since a
+    /// current-version snapshot signed with them could not be imported.
+    pub fn export_snapshot_with_algo(
+        env: Env,
+        caller: Address,
+        checksum_algo: u32,
     ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
         caller.require_auth();
         let config: SplitConfig = env
@@ -552,9 +2928,20 @@ impl RemittanceSplit {
         if config.owner != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
-        let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
+        if checksum_algo == CHECKSUM_ALGO_ADDITIVE || checksum_algo == CHECKSUM_ALGO_ROLLING {
+            return Err(RemittanceSplitError::ChecksumOutdated);
+        }
+        let mut config = config;
+        if !Self::is_bps_mode(env.clone()) {
+            config.spending_percent *= 100;
+            config.savings_percent *= 100;
+            config.bills_percent *= 100;
+            config.insurance_percent *= 100;
+        }
+        let checksum = Self::compute_checksum(&env, SNAPSHOT_VERSION, checksum_algo, &config)?;
         Ok(Some(ExportSnapshot {
             version: SNAPSHOT_VERSION,
+            checksum_algo,
             checksum,
             config,
         }))
@@ -567,13 +2954,31 @@ impl RemittanceSplit {
         snapshot: ExportSnapshot,
     ) -> Result<bool, RemittanceSplitError> {
         caller.require_auth();
+        Self::require_not_paused(&env)?;
         Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_writable(&env)?;
 
-        if snapshot.version != SNAPSHOT_VERSION {
+        if snapshot.version == 0 || snapshot.version > SNAPSHOT_VERSION {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
             return Err(RemittanceSplitError::UnsupportedVersion);
         }
-        let expected = Self::compute_checksum(snapshot.version, &snapshot.config);
+        // Legacy versions predate FNV; current ones must not fall back to weaker algorithms
+        if snapshot.version == SNAPSHOT_VERSION && snapshot.checksum_algo != CHECKSUM_ALGO_FNV {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::ChecksumOutdated);
+        }
+        let expected = match Self::compute_checksum(
+            &env,
+            snapshot.version,
+            snapshot.checksum_algo,
+            &snapshot.config,
+        ) {
+            Ok(c) => c,
+            Err(e) => {
+                Self::append_audit(&env, symbol_short!("import"), &caller, false);
+                return Err(e);
+            }
+        };
         if snapshot.checksum != expected {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
             return Err(RemittanceSplitError::ChecksumMismatch);
@@ -589,28 +2994,54 @@ impl RemittanceSplit {
             return Err(RemittanceSplitError::Unauthorized);
         }
 
-        let total = snapshot.config.spending_percent
-            + snapshot.config.savings_percent
-            + snapshot.config.bills_percent
-            + snapshot.config.insurance_percent;
-        if total != 100 {
+        // Version 1 snapshots hold whole percentages; upgrade them to basis points
+        let mut snapshot = snapshot;
+        if snapshot.version == 1 {
+            let total = snapshot.config.spending_percent as u64
+                + snapshot.config.savings_percent as u64
+                + snapshot.config.bills_percent as u64
+                + snapshot.config.insurance_percent as u64;
+            if total != PERCENT_DENOMINATOR as u64 {
+                Self::append_audit(&env, symbol_short!("import"), &caller, false);
+                return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+            }
+            snapshot.config.spending_percent *= 100;
+            snapshot.config.savings_percent *= 100;
+            snapshot.config.bills_percent *= 100;
+            snapshot.config.insurance_percent *= 100;
+        }
+
+        let total = snapshot.config.spending_percent as u64
+            + snapshot.config.savings_percent as u64
+            + snapshot.config.bills_percent as u64
+            + snapshot.config.insurance_percent as u64;
+        if total != BPS_DENOMINATOR as u64 {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
             return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
         }
+        if snapshot.config.fee_bps > BPS_DENOMINATOR {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::InvalidFeeBps);
+        }
 
         Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BPS_MODE"), &true);
         env.storage()
             .instance()
             .set(&symbol_short!("CONFIG"), &snapshot.config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
+        Self::store_categories(
+            &env,
+            &Self::default_categories(
                 &env,
-                snapshot.config.spending_percent,
-                snapshot.config.savings_percent,
-                snapshot.config.bills_percent,
-                snapshot.config.insurance_percent,
-            ],
+                [
+                    snapshot.config.spending_percent,
+                    snapshot.config.savings_percent,
+                    snapshot.config.bills_percent,
+                    snapshot.config.insurance_percent,
+                ],
+            ),
         );
 
         Self::increment_nonce(&env, &caller)?;
@@ -618,24 +3049,97 @@ impl RemittanceSplit {
         Ok(true)
     }
 
+    /// Audit entries starting at `from_index`, oldest first, at most `MAX_AUDIT_ENTRIES`
+    /// per call. Entries are never dropped; page through with `get_audit_count`.
     pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
-        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
-        let log = log.unwrap_or_else(|| Vec::new(&env));
-        let len = log.len();
+        let len = Self::get_audit_count(env.clone());
         let cap = MAX_AUDIT_ENTRIES.min(limit);
         let mut out = Vec::new(&env);
         if from_index >= len {
             return out;
         }
+        let end = from_index.saturating_add(cap).min(len);
+        let mut index = from_index;
+        while index < end {
+            let chunk_id = index / MAX_AUDIT_ENTRIES;
+            let chunk: Vec<AuditEntry> = env
+                .storage()
+                .persistent()
+                .get(&(symbol_short!("AUDIT"), chunk_id))
+                .unwrap_or_else(|| Vec::new(&env));
+            let chunk_end = ((chunk_id + 1) * MAX_AUDIT_ENTRIES).min(end);
+            for i in index..chunk_end {
+                if let Some(entry) = chunk.get(i % MAX_AUDIT_ENTRIES) {
+                    out.push_back(entry);
+                }
+            }
+            index = chunk_end;
+        }
+        out
+    }
+
+    /// Total number of audit entries recorded
+    pub fn get_audit_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("AUDIT_CNT"))
+            .unwrap_or(0)
+    }
+
+    /// Return distribution records starting at `from_index`, oldest first.
+    /// Only the last `MAX_DISTRIBUTION_RECORDS` distributions are kept.
+    pub fn get_distributions(env: Env, from_index: u32, limit: u32) -> Vec<DistributionRecord> {
+        let log: Vec<DistributionRecord> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("DISTRIBS"))
+            .unwrap_or_else(|| Vec::new(&env));
+        let len = log.len();
+        let cap = MAX_DISTRIBUTION_RECORDS.min(limit);
+        let mut out = Vec::new(&env);
+        if from_index >= len {
+            return out;
+        }
         let end = (from_index + cap).min(len);
         for i in from_index..end {
-            if let Some(entry) = log.get(i) {
+            if let Some(record) = log.get(i) {
+                out.push_back(record);
+            }
+        }
+        out
+    }
+
+    /// Return event summaries with `seq >= from_seq`, oldest first.
+    ///
+    /// Only the most recent `MAX_EVENT_LOG_ENTRIES` summaries are retained; a gap
+    /// between `from_seq` and the first returned `seq` means older entries were dropped.
+    pub fn get_recent_events(env: Env, from_seq: u64, limit: u32) -> Vec<EventLog> {
+        let log: Vec<EventLog> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("EVT_LOG"))
+            .unwrap_or_else(|| Vec::new(&env));
+        let cap = MAX_EVENT_LOG_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        for entry in log.iter() {
+            if out.len() >= cap {
+                break;
+            }
+            if entry.seq >= from_seq {
                 out.push_back(entry);
             }
         }
         out
     }
 
+    /// Sequence number of the most recently recorded event (0 if none).
+    pub fn get_event_seq(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("EVT_SEQ"))
+            .unwrap_or(0)
+    }
+
     fn require_nonce(
         env: &Env,
         address: &Address,
@@ -665,42 +3169,176 @@ impl RemittanceSplit {
         Ok(())
     }
 
-    fn compute_checksum(version: u32, config: &SplitConfig) -> u64 {
-        let v = version as u64;
-        let s = config.spending_percent as u64;
-        let g = config.savings_percent as u64;
-        let b = config.bills_percent as u64;
-        let i = config.insurance_percent as u64;
-        v.wrapping_add(s)
-            .wrapping_add(g)
-            .wrapping_add(b)
-            .wrapping_add(i)
-            .wrapping_mul(31)
+    fn require_initialized(env: &Env) -> Result<(), RemittanceSplitError> {
+        if env.storage().instance().has(&symbol_short!("CONFIG")) {
+            Ok(())
+        } else {
+            Err(RemittanceSplitError::NotInitialized)
+        }
+    }
+
+    /// The four-category amounts `calculate_split` returns, without its events
+    fn split_amounts(env: &Env, total_amount: i128) -> Result<Vec<i128>, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let split = Self::get_split(env);
+        if split.len() != 4 {
+            return Err(RemittanceSplitError::InvalidCategory);
+        }
+        Self::compute_split_amounts(env, total_amount, &split, Self::split_denominator(env))
+    }
+
+    /// Split `total_amount` by `split` shares out of `denominator`; the last entry
+    /// absorbs the rounding remainder.
+    fn compute_split_amounts(
+        env: &Env,
+        total_amount: i128,
+        split: &Vec<u32>,
+        denominator: u32,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let mut amounts = Vec::new(env);
+        let mut allocated: i128 = 0;
+        let last = split.len().saturating_sub(1);
+        for (i, percent) in split.iter().enumerate() {
+            let amount = if i as u32 == last {
+                total_amount.checked_sub(allocated)
+            } else {
+                total_amount
+                    .checked_mul(percent as i128)
+                    .and_then(|n| n.checked_div(denominator as i128))
+            }
+            .ok_or(RemittanceSplitError::Overflow)?;
+            allocated = allocated
+                .checked_add(amount)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            amounts.push_back(amount);
+        }
+        Ok(amounts)
+    }
+
+    fn compute_checksum(
+        env: &Env,
+        version: u32,
+        checksum_algo: u32,
+        config: &SplitConfig,
+    ) -> Result<u64, RemittanceSplitError> {
+        let fields = [
+            version as u64,
+            config.spending_percent as u64,
+            config.savings_percent as u64,
+            config.bills_percent as u64,
+            config.insurance_percent as u64,
+        ];
+        match checksum_algo {
+            CHECKSUM_ALGO_ADDITIVE => Ok(fields
+                .iter()
+                .fold(0u64, |acc, f| acc.wrapping_add(*f))
+                .wrapping_mul(31)),
+            CHECKSUM_ALGO_ROLLING => Ok(fields
+                .iter()
+                .fold(17u64, |acc, f| acc.wrapping_mul(31).wrapping_add(*f))),
+            CHECKSUM_ALGO_FNV => {
+                const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+                const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+                let mut hash = FNV_OFFSET;
+                let mut feed = |byte: u8| {
+                    hash ^= byte as u64;
+                    hash = hash.wrapping_mul(FNV_PRIME);
+                };
+                for byte in config.owner.clone().to_xdr(env).iter() {
+                    feed(byte);
+                }
+                for (i, field) in fields.iter().enumerate() {
+                    for byte in field.wrapping_mul(i as u64 + 1).to_le_bytes() {
+                        feed(byte);
+                    }
+                }
+                for byte in config.fee_bps.to_le_bytes() {
+                    feed(byte);
+                }
+                match &config.fee_collector {
+                    Some(collector) => {
+                        feed(1);
+                        for byte in collector.clone().to_xdr(env).iter() {
+                            feed(byte);
+                        }
+                    }
+                    None => feed(0),
+                }
+                Ok(hash)
+            }
+            _ => Err(RemittanceSplitError::UnsupportedChecksumAlgo),
+        }
     }
 
     fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
+        Self::append_audit_with_amount(env, operation, caller, success, 0);
+    }
+
+    fn append_distribution(env: &Env, record: DistributionRecord) {
+        let mut log: Vec<DistributionRecord> = env
             .storage()
             .instance()
-            .get(&symbol_short!("AUDIT"))
+            .get(&symbol_short!("DISTRIBS"))
             .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
-                }
-            }
-            log = new_log;
+        if log.len() >= MAX_DISTRIBUTION_RECORDS {
+            log.pop_front();
         }
-        log.push_back(AuditEntry {
+        log.push_back(record);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DISTRIBS"), &log);
+    }
+
+    fn append_audit_with_amount(
+        env: &Env,
+        operation: Symbol,
+        caller: &Address,
+        success: bool,
+        amount: i128,
+    ) {
+        let timestamp = env.ledger().timestamp();
+        let count = Self::get_audit_count(env.clone());
+        // Each chunk holds MAX_AUDIT_ENTRIES; a full chunk rolls over to the next key
+        let key = (symbol_short!("AUDIT"), count / MAX_AUDIT_ENTRIES);
+        let storage = env.storage().persistent();
+        let mut chunk: Vec<AuditEntry> = storage.get(&key).unwrap_or_else(|| Vec::new(env));
+        chunk.push_back(AuditEntry {
             operation,
             caller: caller.clone(),
             timestamp,
             success,
+            amount,
+        });
+        storage.set(&key, &chunk);
+        storage.extend_ttl(&key, AUDIT_LIFETIME_THRESHOLD, AUDIT_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("AUDIT_CNT"), &(count + 1));
+    }
+
+    fn record_event(env: &Env, kind: Symbol) {
+        let seq = Self::get_event_seq(env.clone()).saturating_add(1);
+        let mut log: Vec<EventLog> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("EVT_LOG"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_EVENT_LOG_ENTRIES {
+            log.pop_front();
+        }
+        log.push_back(EventLog {
+            seq,
+            kind,
+            timestamp: env.ledger().timestamp(),
         });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("EVT_LOG"), &log);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("EVT_SEQ"), &seq);
     }
 
     fn extend_instance_ttl(env: &Env) {
@@ -709,6 +3347,12 @@ impl RemittanceSplit {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Extend instance TTL without mutating the split config. Callable by anyone, e.g. keepers.
+    pub fn bump_ttl(env: Env) -> bool {
+        Self::extend_instance_ttl(&env);
+        true
+    }
+
     pub fn create_remittance_schedule(
         env: Env,
         owner: Address,
@@ -728,12 +3372,25 @@ impl RemittanceSplit {
         }
 
         Self::extend_instance_ttl(&env);
+        Ok(Self::insert_schedule(
+            &env, &owner, amount, next_due, interval,
+        ))
+    }
 
+    /// Store a new active schedule and return its id
+    fn insert_schedule(
+        env: &Env,
+        owner: &Address,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> u32 {
+        let current_time = env.ledger().timestamp();
         let mut schedules: Map<u32, RemittanceSchedule> = env
             .storage()
             .instance()
             .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
 
         let next_schedule_id = env
             .storage()
@@ -765,10 +3422,11 @@ impl RemittanceSplit {
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Created),
-            (next_schedule_id, owner),
+            (next_schedule_id, owner.clone()),
         );
+        Self::record_event(env, symbol_short!("sch_new"));
 
-        Ok(next_schedule_id)
+        next_schedule_id
     }
 
     pub fn modify_remittance_schedule(
@@ -820,6 +3478,7 @@ impl RemittanceSplit {
             (symbol_short!("schedule"), ScheduleEvent::Modified),
             (schedule_id, caller),
         );
+        Self::record_event(&env, symbol_short!("sch_mod"));
 
         Ok(true)
     }
@@ -830,20 +3489,28 @@ impl RemittanceSplit {
         schedule_id: u32,
     ) -> Result<bool, RemittanceSplitError> {
         caller.require_auth();
+        Self::deactivate_schedule(&env, &caller, schedule_id)
+    }
 
-        Self::extend_instance_ttl(&env);
+    /// Marks `caller`'s schedule inactive; `caller` must already be authorized
+    fn deactivate_schedule(
+        env: &Env,
+        caller: &Address,
+        schedule_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        Self::extend_instance_ttl(env);
 
         let mut schedules: Map<u32, RemittanceSchedule> = env
             .storage()
             .instance()
             .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
 
         let mut schedule = schedules
             .get(schedule_id)
             .ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
-        if schedule.owner != caller {
+        if schedule.owner != *caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
 
@@ -856,8 +3523,9 @@ impl RemittanceSplit {
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Cancelled),
-            (schedule_id, caller),
+            (schedule_id, caller.clone()),
         );
+        Self::record_event(env, symbol_short!("sch_can"));
 
         Ok(true)
     }
@@ -887,6 +3555,165 @@ impl RemittanceSplit {
 
         schedules.get(schedule_id)
     }
+
+    /// Schedule the owner's split of `amount` to `accounts`, first at `next_run` and
+    /// then every `interval` seconds (0 runs once). The schedule is an ordinary
+    /// `RemittanceSchedule` (see `get_remittance_schedule`) that also records the
+    /// token and recipients `execute_scheduled` pays. Config owner only.
+    ///
+    /// # Returns
+    /// The new schedule's id
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_distribution(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        usdc_contract: Address,
+        accounts: AccountGroup,
+        amount: i128,
+        next_run: u64,
+        interval: u64,
+    ) -> Result<u32, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_owner(&env, &caller, symbol_short!("sch_dist"))?;
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("sch_dist"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if next_run <= env.ledger().timestamp() {
+            Self::append_audit(&env, symbol_short!("sch_dist"), &caller, false);
+            return Err(RemittanceSplitError::InvalidDueDate);
+        }
+        if !Self::recipients_allowed(&env, &accounts) {
+            Self::append_audit(&env, symbol_short!("sch_dist"), &caller, false);
+            return Err(RemittanceSplitError::RecipientsLocked);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let schedule_id = Self::insert_schedule(&env, &caller, amount, next_run, interval);
+        let mut targets = Self::schedule_targets(&env);
+        targets.set(schedule_id, (usdc_contract, accounts));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SCH_DIST"), &targets);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("sch_dist"), &caller, true);
+        Ok(schedule_id)
+    }
+
+    /// Token and recipients of each schedule created by `schedule_distribution`
+    fn schedule_targets(env: &Env) -> Map<u32, (Address, AccountGroup)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SCH_DIST"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Run scheduled distribution `schedule_id` if it is due, through the same
+    /// pipeline as `distribute_usdc` (platform fee, category minimum, dust folding,
+    /// caps, distinct and locked recipients, daily cap). The schedule owner must
+    /// authorize the transfers and still be the config owner or an operator.
+    /// Returns false when the schedule is unknown, cancelled or not yet due, when the
+    /// daily cap would be exceeded, or when a recipient can't receive the token.
+    pub fn execute_scheduled(env: Env, schedule_id: u32) -> Result<bool, RemittanceSplitError> {
+        let mut schedules: Map<u32, RemittanceSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("REM_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+        let (mut schedule, (usdc_contract, accounts)) = match (
+            schedules.get(schedule_id),
+            Self::schedule_targets(&env).get(schedule_id),
+        ) {
+            (Some(schedule), Some(target)) if schedule.active => (schedule, target),
+            _ => return Ok(false),
+        };
+        let now = env.ledger().timestamp();
+        if now < schedule.next_due {
+            return Ok(false);
+        }
+        schedule.owner.require_auth();
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != schedule.owner && !Self::is_operator(env.clone(), schedule.owner.clone())
+        {
+            Self::append_audit(&env, symbol_short!("sch_exec"), &schedule.owner, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        if Self::run_distribution(
+            &env,
+            &usdc_contract,
+            &schedule.owner,
+            accounts,
+            schedule.amount,
+            symbol_short!("sch_exec"),
+        )?
+        .is_none()
+        {
+            return Ok(false);
+        }
+
+        Self::extend_instance_ttl(&env);
+        schedule.last_executed = Some(now);
+        if schedule.recurring {
+            schedule.next_due = now + schedule.interval;
+        } else {
+            schedule.active = false;
+        }
+        let owner = schedule.owner.clone();
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("REM_SCH"), &schedules);
+        env.events().publish(
+            (symbol_short!("schedule"), ScheduleEvent::Executed),
+            (schedule_id, owner),
+        );
+        Self::record_event(&env, symbol_short!("sch_run"));
+        Ok(true)
+    }
+
+    /// Cancel scheduled distribution `schedule_id` so `execute_scheduled` does nothing
+    /// (schedule owner only, nonce-protected and audited).
+    pub fn cancel_schedule(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        schedule_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        let schedule = match Self::get_schedule(env.clone(), schedule_id) {
+            Some(schedule) => schedule,
+            None => {
+                Self::append_audit(&env, symbol_short!("sch_cncl"), &caller, false);
+                return Err(RemittanceSplitError::ScheduleNotFound);
+            }
+        };
+        if schedule.owner != caller {
+            Self::append_audit(&env, symbol_short!("sch_cncl"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::deactivate_schedule(&env, &caller, schedule_id)?;
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("sch_cncl"), &caller, true);
+        Ok(true)
+    }
+
+    /// Scheduled distribution `schedule_id` and its `next_due`, while it is still active
+    pub fn get_schedule(env: Env, schedule_id: u32) -> Option<RemittanceSchedule> {
+        if !Self::schedule_targets(&env).contains_key(schedule_id) {
+            return None;
+        }
+        Self::get_remittance_schedule(env, schedule_id).filter(|schedule| schedule.active)
+    }
 }
 
 mod test;
@@ -94,8 +94,17 @@ fn test_update_split() {
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
+    let result = client.try_update_split(&owner, &1, &u32::MAX, &1, &0, &100);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
     let success = client.update_split(&owner, &1, &40, &40, &10, &10);
     assert_eq!(success, true);
+    assert_eq!(client.get_next_nonce(&owner), 2);
+    let result = client.try_update_split(&owner, &1, &40, &40, &10, &10);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidNonce)));
 
     let config = client.get_config().unwrap();
     assert_eq!(config.spending_percent, 40);
@@ -419,3 +428,2409 @@ fn test_calculate_split_events() {
     let data: i128 = i128::try_from_val(&env, &last_event.2).unwrap();
     assert_eq!(data, total_amount);
 }
+
+#[test]
+fn test_preview_split_with_matches_calculate_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Works before initialization
+    let preview = client.preview_split_with(&1003, &17, &19, &23, &41);
+    assert!(client.get_config().is_none());
+
+    client.initialize_split(&owner, &0, &17, &19, &23, &41);
+    let stored = client.calculate_split(&1003);
+    assert_eq!(preview, stored);
+
+    let result = client.try_preview_split_with(&1000, &50, &30, &15, &10);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    // Percentages that would overflow a u32 sum are rejected, not a panic
+    let result = client.try_preview_split_with(&1000, &u32::MAX, &1, &0, &100);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+}
+
+#[test]
+fn test_get_recent_events_consecutive_seq() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+
+    let events = client.get_recent_events(&0, &10);
+    assert_eq!(events.len(), 2);
+    let first = events.get(0).unwrap();
+    let second = events.get(1).unwrap();
+    assert_eq!(first.kind, symbol_short!("init"));
+    assert_eq!(second.kind, symbol_short!("update"));
+    assert_eq!(second.seq, first.seq + 1);
+    assert_eq!(second.timestamp, 1000);
+    assert_eq!(client.get_event_seq(), second.seq);
+
+    // Resuming from the last seen sequence returns only newer summaries
+    let newer = client.get_recent_events(&second.seq, &10);
+    assert_eq!(newer.len(), 1);
+}
+
+#[test]
+fn test_every_published_event_is_recorded() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let last_kind = || client.get_recent_events(&0, &100).last().unwrap().kind;
+
+    client.set_min_transfer(&owner, &1, &10);
+    assert_eq!(last_kind(), symbol_short!("min_xfer"));
+    client.set_allow_duplicate_recipients(&owner, &2, &true);
+    assert_eq!(last_kind(), symbol_short!("dup_recip"));
+    client.grant_operator(&owner, &3, &operator);
+    assert_eq!(last_kind(), symbol_short!("op_grant"));
+    client.revoke_operator(&owner, &4, &operator);
+    assert_eq!(last_kind(), symbol_short!("op_revoke"));
+    client.set_accounts(&owner, &5, &accounts);
+    assert_eq!(last_kind(), symbol_short!("set_accts"));
+    client.set_readonly_after(&owner, &6, &u64::MAX);
+    assert_eq!(last_kind(), symbol_short!("readonly"));
+    client.reset_config(&owner, &7);
+    assert_eq!(last_kind(), symbol_short!("rst_cfg"));
+}
+
+#[test]
+fn test_snapshot_round_trip_each_checksum_algo() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let latest = client.export_snapshot(&owner).unwrap();
+    assert_eq!(latest.checksum_algo, CHECKSUM_ALGO_FNV);
+    assert!(client.import_snapshot(&owner, &1, &latest));
+
+    // Weaker checksums are no longer exported
+    for algo in [CHECKSUM_ALGO_ADDITIVE, CHECKSUM_ALGO_ROLLING] {
+        let result = client.try_export_snapshot_with_algo(&owner, &algo);
+        assert!(matches!(
+            result,
+            Err(Ok(RemittanceSplitError::ChecksumOutdated))
+        ));
+    }
+    let fnv = client
+        .export_snapshot_with_algo(&owner, &CHECKSUM_ALGO_FNV)
+        .unwrap();
+    assert!(client.import_snapshot(&owner, &2, &fnv));
+
+    let result = client.try_export_snapshot_with_algo(&owner, &99);
+    assert!(matches!(
+        result,
+        Err(Ok(RemittanceSplitError::UnsupportedChecksumAlgo))
+    ));
+}
+
+#[test]
+fn test_snapshot_cross_algo_tampering_detected() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // Relabelling an FNV snapshot as legacy is refused outright
+    let mut relabelled = client.export_snapshot(&owner).unwrap();
+    relabelled.checksum_algo = CHECKSUM_ALGO_ADDITIVE;
+    let result = client.try_import_snapshot(&owner, &1, &relabelled);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ChecksumOutdated)));
+
+    // Reordering percentages changes the FNV digest
+    let mut reordered = client.export_snapshot(&owner).unwrap();
+    reordered.config.spending_percent = 3000;
+    reordered.config.savings_percent = 5000;
+    let result = client.try_import_snapshot(&owner, &1, &reordered);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ChecksumMismatch)));
+
+    // So does swapping in another owner
+    let mut reowned = client.export_snapshot(&owner).unwrap();
+    reowned.config.owner = Address::generate(&env);
+    let result = client.try_import_snapshot(&owner, &1, &reowned);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ChecksumMismatch)));
+}
+
+#[test]
+fn test_freeze_window_blocks_distribution_only_inside_window() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &3_000);
+
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    client.set_freeze_window(&owner, &1, &2_000, &3_000);
+
+    // Before the window
+    set_time(&env, 1_000);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &2, &accounts, &1_000, &false)
+        .is_some());
+
+    // Inside the window: blocked, but config updates still work
+    set_time(&env, 2_500);
+    let result =
+        client.try_distribute_usdc(&token.address(), &owner, &3, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::DistributionsFrozen)));
+    // update_split consumes the nonce like every other nonce-taking call
+    assert!(client.update_split(&owner, &3, &40, &30, &20, &10));
+
+    // After the window
+    set_time(&env, 3_000);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &4, &accounts, &1_000, &false)
+        .is_some());
+
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 500 + 400);
+    assert_eq!(token_client.balance(&owner), 1_000);
+
+    // Clearing the window lifts the freeze immediately
+    client.set_freeze_window(&owner, &5, &3_000, &4_000);
+    assert!(client.get_freeze_window().is_some());
+    client.clear_freeze_window(&owner, &6);
+    assert!(client.get_freeze_window().is_none());
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &7, &accounts, &1_000, &false)
+        .is_some());
+}
+
+#[test]
+fn test_set_freeze_window_rejects_invalid_range() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let result = client.try_set_freeze_window(&owner, &1, &5_000, &5_000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFreezeWindow)));
+}
+
+#[test]
+fn test_get_split_bps() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    // Uninitialized fallback, expressed in bps
+    let bps = client.get_split_bps();
+    assert_eq!(bps, vec![&env, 5000, 3000, 1500, 500]);
+
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    let bps = client.get_split_bps();
+    assert_eq!(bps, vec![&env, 4000, 3000, 2000, 1000]);
+}
+
+#[test]
+fn test_bump_ttl_requires_no_auth_and_keeps_config() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    assert!(client.bump_ttl());
+    // No authorization was required for the bump
+    assert!(env.auths().is_empty());
+
+    let config = client.get_config().unwrap();
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.spending_percent, 50);
+    assert_eq!(client.get_split(), vec![&env, 50, 30, 15, 5]);
+    assert_eq!(client.get_nonce(&owner), 1);
+}
+
+#[test]
+fn test_daily_volume_buckets() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    // Day 10: two distributions
+    set_time(&env, 10 * 86400 + 100);
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false);
+    set_time(&env, 10 * 86400 + 86399);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &500, &false);
+
+    // Day 12: one distribution
+    set_time(&env, 12 * 86400);
+    client.distribute_usdc(&token.address(), &owner, &3, &accounts, &2_000, &false);
+
+    assert_eq!(client.get_daily_volume(&10), 1_500);
+    assert_eq!(client.get_daily_volume(&11), 0);
+    assert_eq!(client.get_daily_volume(&12), 2_000);
+    assert_eq!(
+        client.get_volume_range(&10, &12),
+        vec![&env, 1_500i128, 0, 2_000]
+    );
+    assert_eq!(client.get_volume_range(&12, &10).len(), 0);
+}
+
+#[test]
+fn test_validate_accounts_reports_problem_categories() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let sender = Address::generate(&env);
+    let shared = Address::generate(&env);
+
+    let good = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    assert_eq!(
+        client.validate_accounts(&good, &Some(sender.clone())).len(),
+        0
+    );
+
+    let bad = AccountGroup {
+        spending: sender.clone(),
+        savings: shared.clone(),
+        bills: shared,
+        insurance: Address::generate(&env),
+    };
+    assert_eq!(
+        client.validate_accounts(&bad, &None),
+        vec![&env, symbol_short!("SAVINGS"), symbol_short!("BILLS")]
+    );
+    assert_eq!(
+        client.validate_accounts(&bad, &Some(sender)),
+        vec![
+            &env,
+            symbol_short!("SPENDING"),
+            symbol_short!("SAVINGS"),
+            symbol_short!("BILLS")
+        ]
+    );
+}
+
+#[test]
+fn test_distribution_audit_entry_records_amount() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &5_000);
+
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &4_200, &false);
+
+    let log = client.get_audit_log(&0, &100);
+    let init_entry = log.get(0).unwrap();
+    assert_eq!(init_entry.operation, symbol_short!("init"));
+    assert_eq!(init_entry.amount, 0);
+
+    let newest = log.get(log.len() - 1).unwrap();
+    assert_eq!(newest.operation, symbol_short!("distrib"));
+    assert!(newest.success);
+    assert_eq!(newest.amount, 4_200);
+}
+
+#[test]
+fn test_distribute_usdc_by_alias() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &2_000);
+
+    let mom = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let utility = Address::generate(&env);
+    let insurer = Address::generate(&env);
+    client.set_alias(&owner, &symbol_short!("mom"), &mom);
+    client.set_alias(&owner, &symbol_short!("bank"), &bank);
+    client.set_alias(&owner, &symbol_short!("utility"), &utility);
+    client.set_alias(&owner, &symbol_short!("insurer"), &insurer);
+    assert_eq!(client.get_alias(&symbol_short!("mom")), Some(mom.clone()));
+    assert_eq!(client.get_alias(&symbol_short!("nobody")), None);
+
+    assert!(client
+        .distribute_usdc_by_alias(
+            &token.address(),
+            &owner,
+            &1,
+            &symbol_short!("mom"),
+            &symbol_short!("bank"),
+            &symbol_short!("utility"),
+            &symbol_short!("insurer"),
+            &1_000,
+        )
+        .is_some());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&mom), 500);
+    assert_eq!(token_client.balance(&bank), 300);
+    assert_eq!(token_client.balance(&utility), 150);
+    assert_eq!(token_client.balance(&insurer), 50);
+
+    let result = client.try_distribute_usdc_by_alias(
+        &token.address(),
+        &owner,
+        &2,
+        &symbol_short!("mom"),
+        &symbol_short!("nobody"),
+        &symbol_short!("utility"),
+        &symbol_short!("insurer"),
+        &1_000,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::AliasNotFound)));
+}
+
+#[test]
+fn test_get_next_nonce_matches_consumed_nonce() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let next = client.get_next_nonce(&owner);
+    assert_eq!(next, 0);
+    client.initialize_split(&owner, &next, &50, &30, &15, &5);
+
+    let next = client.get_next_nonce(&owner);
+    assert_eq!(next, 1);
+    let stale = client.try_set_freeze_window(&owner, &0, &10, &20);
+    assert_eq!(stale, Err(Ok(RemittanceSplitError::InvalidNonce)));
+    client.set_freeze_window(&owner, &next, &10, &20);
+    assert_eq!(client.get_next_nonce(&owner), 2);
+}
+
+#[test]
+fn test_config_age_and_staleness() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    assert_eq!(client.get_config_age(&5_000), 0);
+    assert!(!client.is_config_stale(&5_000, &1));
+
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let year = 365 * 86400u64;
+    set_time(&env, 1_000 + year - 1);
+    // Updating the allocations does not reset the creation time
+    client.update_split(&owner, &1, &40, &40, &15, &5);
+    assert_eq!(client.get_config_age(&(1_000 + year - 1)), year - 1);
+    assert!(!client.is_config_stale(&(1_000 + year - 1), &year));
+
+    assert_eq!(client.get_config_age(&(1_000 + year)), year);
+    assert!(client.is_config_stale(&(1_000 + year), &year));
+}
+
+#[test]
+fn test_get_skipped_categories_reports_zero_buckets() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // 6 splits as [3, 1, 0, 2]: bills rounds to zero, insurance takes the remainder
+    let skipped = client.get_skipped_categories(&6);
+    assert_eq!(skipped, vec![&env, symbol_short!("BILLS")]);
+
+    // 2 splits as [1, 0, 0, 1]
+    let skipped = client.get_skipped_categories(&2);
+    assert_eq!(
+        skipped,
+        vec![&env, symbol_short!("SAVINGS"), symbol_short!("BILLS")]
+    );
+
+    assert_eq!(client.get_skipped_categories(&1000).len(), 0);
+    assert_eq!(client.get_skipped_categories(&0).len(), 4);
+}
+
+#[test]
+fn test_distribute_usdc_requires_initialized_split() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let payer = Address::generate(&env);
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&payer, &1_000);
+
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result =
+        client.try_distribute_usdc(&token.address(), &payer, &0, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&payer),
+        1_000
+    );
+
+    // Explicit bypass uses the default 50/30/15/5 split
+    assert!(client
+        .distribute_usdc(&token.address(), &payer, &0, &accounts, &1_000, &true)
+        .is_some());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 500);
+    assert_eq!(token_client.balance(&accounts.insurance), 50);
+
+    // Reads keep falling back to the default
+    assert_eq!(client.calculate_split(&100), vec![&env, 50, 30, 15, 5]);
+}
+
+#[test]
+fn test_readonly_after_blocks_mutations_but_not_reads() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    client.set_readonly_after(&owner, &1, &5_000);
+    assert_eq!(client.get_readonly_after(), Some(5_000));
+
+    // Before the cutoff everything still works
+    assert!(client.update_split(&owner, &2, &40, &40, &10, &10));
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &3, &accounts, &1_000, &false)
+        .is_some());
+    let snapshot = client.export_snapshot(&owner).unwrap();
+
+    set_time(&env, 5_000);
+    let result = client.try_update_split(&owner, &4, &50, &30, &15, &5);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ReadOnly)));
+    let result =
+        client.try_distribute_usdc(&token.address(), &owner, &4, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ReadOnly)));
+    let result = client.try_import_snapshot(&owner, &4, &snapshot);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ReadOnly)));
+
+    // Reads are unaffected
+    assert_eq!(client.get_split(), vec![&env, 40, 40, 10, 10]);
+    assert_eq!(client.calculate_split(&100), vec![&env, 40, 40, 10, 10]);
+}
+
+#[test]
+fn test_is_nonce_valid_only_for_current_nonce() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    assert!(client.is_nonce_valid(&owner, &0));
+    assert!(!client.is_nonce_valid(&owner, &1));
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    assert!(!client.is_nonce_valid(&owner, &0));
+    assert!(client.is_nonce_valid(&owner, &1));
+    assert!(!client.is_nonce_valid(&owner, &5));
+    assert!(env.auths().is_empty());
+}
+
+#[test]
+fn test_get_distributions_records_recipients_and_amounts() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+
+    let first = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let second = AccountGroup {
+        spending: Address::generate(&env),
+        savings: first.savings.clone(),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    set_time(&env, 1_000);
+    client.distribute_usdc(&token.address(), &owner, &1, &first, &1_000, &false);
+    set_time(&env, 2_000);
+    client.distribute_usdc(&token.address(), &owner, &2, &second, &2_000, &false);
+
+    let records = client.get_distributions(&0, &10);
+    assert_eq!(records.len(), 2);
+
+    let r0 = records.get(0).unwrap();
+    assert_eq!(r0.timestamp, 1_000);
+    assert_eq!(r0.total, 1_000);
+    assert_eq!(r0.spending_to, first.spending);
+    assert_eq!(r0.insurance_to, first.insurance);
+    assert_eq!(r0.amounts, vec![&env, 500, 300, 150, 50]);
+
+    let r1 = records.get(1).unwrap();
+    assert_eq!(r1.total, 2_000);
+    assert_eq!(r1.spending_to, second.spending);
+    assert_eq!(r1.savings_to, first.savings);
+    assert_eq!(r1.bills_to, second.bills);
+    assert_eq!(r1.amounts, vec![&env, 1_000, 600, 300, 100]);
+
+    assert_eq!(client.get_distributions(&1, &10).len(), 1);
+}
+
+/// Overwrite the stored SPLIT vector directly, leaving CONFIG untouched.
+fn desync_split(env: &Env, contract_id: &Address, split: Vec<u32>) {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT"), &split);
+    });
+}
+
+#[test]
+fn test_repair_split_restores_split_from_config() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    assert!(client.verify_integrity());
+
+    desync_split(&env, &contract_id, vec![&env, 70, 10, 10, 10]);
+    assert!(!client.verify_integrity());
+    assert_eq!(client.get_split(), vec![&env, 70, 10, 10, 10]);
+
+    let result = client.try_repair_split(&other, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    assert!(client.repair_split(&owner, &1));
+    assert!(client.verify_integrity());
+    assert_eq!(client.get_split(), vec![&env, 40, 30, 20, 10]);
+    assert_eq!(client.get_nonce(&owner), 2);
+}
+
+#[test]
+fn test_estimate_transfers_skips_zero_buckets() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // 2 splits as [1, 0, 0, 1]: savings and bills round to zero
+    assert_eq!(client.estimate_transfers(&2), 2);
+    assert_eq!(client.estimate_transfers(&1000), 4);
+    assert_eq!(client.estimate_transfers(&0), 0);
+
+    // The fee adds a transfer; insurance (50 of the 975 net) folds into spending
+    client.update_split_with_fee(
+        &owner,
+        &1,
+        &50,
+        &30,
+        &15,
+        &5,
+        &250,
+        &Address::generate(&env),
+    );
+    client.set_min_transfer(&owner, &2, &100);
+    let events_before = client.get_event_seq();
+    assert_eq!(client.estimate_transfers(&1000), 4);
+    assert_eq!(client.estimate_transfers(&100_000), 5);
+    assert_eq!(client.get_event_seq(), events_before);
+}
+
+#[test]
+fn test_pause_distributions_keeps_config_updates_open() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result = client.try_pause_distributions(&other);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    client.pause_distributions(&owner);
+    assert!(client.is_distributions_paused());
+    assert!(!client.is_paused());
+
+    let result =
+        client.try_distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::DistributionsPaused)));
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&owner),
+        10_000
+    );
+
+    // Config updates and reads still work
+    assert!(client.update_split(&owner, &1, &40, &40, &10, &10));
+    assert_eq!(client.calculate_split(&100), vec![&env, 40, 40, 10, 10]);
+
+    client.resume_distributions(&owner);
+    assert!(!client.is_distributions_paused());
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &2, &accounts, &1_000, &false)
+        .is_some());
+}
+
+#[test]
+fn test_distribute_usdc_rejects_recipient_that_cannot_receive() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    let asset = StellarAssetClient::new(&env, &token.address());
+    asset.mint(&owner, &10_000);
+    token
+        .issuer()
+        .set_flag(soroban_sdk::testutils::IssuerFlags::RevocableFlag);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    asset.set_authorized(&accounts.bills, &false);
+
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false)
+        .is_none());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&owner), 10_000);
+    assert_eq!(token_client.balance(&accounts.spending), 0);
+
+    let log = client.get_audit_log(&0, &50);
+    let last = log.get(log.len() - 1).unwrap();
+    assert_eq!(last.operation, symbol_short!("BILLS"));
+    assert!(!last.success);
+    assert_eq!(last.amount, 150);
+
+    // Nonce was not consumed: retry succeeds once the recipient is authorized
+    asset.set_authorized(&accounts.bills, &true);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false)
+        .is_some());
+    assert_eq!(token_client.balance(&accounts.bills), 150);
+}
+
+#[test]
+fn test_resolve_distribution_applies_fee_and_cap() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let collector = Address::generate(&env);
+    let result =
+        client.try_update_split_with_fee(&owner, &1, &50, &30, &15, &5, &10_001, &collector);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFeeBps)));
+    let result = client.try_set_category_cap(&other, &0, &symbol_short!("SAVINGS"), &2_000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    client.update_split_with_fee(&owner, &1, &50, &30, &15, &5, &100, &collector);
+    client.set_category_cap(&owner, &2, &symbol_short!("SAVINGS"), &2_000);
+    assert_eq!(client.get_category_cap(&symbol_short!("SAVINGS")), 2_000);
+
+    // 1% fee leaves 9_900 to split as [4_950, 2_970, 1_485, 495];
+    // savings is cut to 2_000 and the 970 overflow goes to spending
+    let resolved = client.resolve_distribution(&10_000);
+    assert_eq!(resolved.len(), 5);
+    let expected = [
+        (symbol_short!("SPENDING"), 5_920),
+        (symbol_short!("SAVINGS"), 2_000),
+        (symbol_short!("BILLS"), 1_485),
+        (symbol_short!("INSURANCE"), 495),
+        (symbol_short!("FEE"), 100),
+    ];
+    for (i, (category, amount)) in expected.into_iter().enumerate() {
+        let allocation = resolved.get(i as u32).unwrap();
+        assert_eq!(allocation.category, category);
+        assert_eq!(allocation.amount, amount);
+    }
+
+    // Removing the cap restores the plain split
+    client.set_category_cap(&owner, &3, &symbol_short!("SAVINGS"), &0);
+    assert_eq!(
+        client.resolve_distribution(&10_000).get(1).unwrap().amount,
+        2_970
+    );
+    assert_eq!(client.resolve_distribution(&0).len(), 0);
+}
+
+#[test]
+fn test_distribute_usdc_applies_category_caps_like_resolve_distribution() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let token_client = TokenClient::new(&env, &token.address());
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    // Savings is cut from 300 to 200 and spending takes the excess
+    client.set_category_cap(&owner, &1, &symbol_short!("SAVINGS"), &200);
+    let resolved = client.resolve_distribution(&1_000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1_000, &false);
+    let recipients = [
+        &accounts.spending,
+        &accounts.savings,
+        &accounts.bills,
+        &accounts.insurance,
+    ];
+    for (recipient, allocation) in recipients.iter().zip(resolved.iter()) {
+        assert_eq!(token_client.balance(recipient), allocation.amount);
+    }
+    assert_eq!(token_client.balance(&accounts.spending), 600);
+    assert_eq!(token_client.balance(&accounts.savings), 200);
+
+    // With every category capped the excess stays with the sender
+    for (nonce, category) in [
+        (3, symbol_short!("SPENDING")),
+        (4, symbol_short!("BILLS")),
+        (5, symbol_short!("INSURANCE")),
+    ] {
+        client.set_category_cap(&owner, &nonce, &category, &100);
+    }
+    let receipt = client
+        .distribute_usdc(&token.address(), &owner, &6, &accounts, &1_000, &false)
+        .unwrap();
+    let overflow = receipt.allocations.get(4).unwrap();
+    assert_eq!(overflow.category, symbol_short!("OVERFLOW"));
+    // 500/300/150/50 capped at 100/200/100/100 moves only 450
+    assert_eq!(overflow.amount, 550);
+    assert_eq!(token_client.balance(&owner), 10_000 - 1_000 - 450);
+    assert_eq!(
+        client.resolve_distribution(&1_000).get(5).unwrap(),
+        overflow
+    );
+}
+
+#[test]
+fn test_operator_can_distribute_but_not_update_split() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&operator, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    // Not yet an operator
+    let result =
+        client.try_distribute_usdc(&token.address(), &operator, &0, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    let result = client.try_grant_operator(&operator, &0, &operator);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    client.grant_operator(&owner, &1, &operator);
+    assert!(client.is_operator(&operator));
+    let log = client.get_audit_log(&0, &50);
+    assert_eq!(
+        log.get(log.len() - 1).unwrap().operation,
+        symbol_short!("op_grant")
+    );
+
+    assert!(client
+        .distribute_usdc(&token.address(), &operator, &0, &accounts, &1_000, &false)
+        .is_some());
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&accounts.spending),
+        500
+    );
+
+    let result = client.try_update_split(&operator, &1, &40, &40, &10, &10);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    client.revoke_operator(&owner, &2, &operator);
+    assert!(!client.is_operator(&operator));
+    let result =
+        client.try_distribute_usdc(&token.address(), &operator, &1, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+}
+
+#[test]
+fn test_distribute_once_uses_inline_split() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let sender = Address::generate(&env);
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&sender, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result = client.try_distribute_once(
+        &sender,
+        &0,
+        &token.address(),
+        &accounts,
+        &1_000,
+        &25,
+        &25,
+        &25,
+        &20,
+    );
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    assert!(client.distribute_once(
+        &sender,
+        &0,
+        &token.address(),
+        &accounts,
+        &1_000,
+        &25,
+        &25,
+        &25,
+        &25
+    ));
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 250);
+    assert_eq!(token_client.balance(&accounts.savings), 250);
+    assert_eq!(token_client.balance(&accounts.bills), 250);
+    assert_eq!(token_client.balance(&accounts.insurance), 250);
+
+    // Stored config untouched, nonce consumed
+    assert!(client.get_config().is_none());
+    assert_eq!(client.get_nonce(&sender), 1);
+}
+
+#[test]
+fn test_reset_requires_opt_in_and_allows_fresh_initialize() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_upgrade_admin(&owner, &admin);
+    assert!(client.is_initialized());
+
+    // Not armed yet
+    let result = client.try_reset(&admin);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ResetDisabled)));
+    let result = client.try_set_reset_enabled(&owner, &true);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    client.set_reset_enabled(&admin, &true);
+    assert!(client.reset(&admin));
+    assert!(!client.is_initialized());
+    assert!(client.get_config().is_none());
+    assert_eq!(client.get_nonce(&owner), 0);
+    assert_eq!(client.get_audit_log(&0, &50).len(), 0);
+
+    // Disarmed again after one use
+    assert!(!client.is_reset_enabled());
+    let result = client.try_reset(&admin);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ResetDisabled)));
+
+    assert!(client.initialize_split(&owner, &0, &40, &40, &10, &10));
+    assert_eq!(client.calculate_split(&100), vec![&env, 40, 40, 10, 10]);
+}
+
+#[test]
+fn test_get_received_total_accumulates_per_recipient() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    assert_eq!(client.get_received_total(&accounts.savings), 0);
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &2_000, &false);
+
+    // 30% of 1_000 plus 30% of 2_000
+    assert_eq!(client.get_received_total(&accounts.savings), 300 + 600);
+    assert_eq!(client.get_received_total(&accounts.spending), 1_500);
+    assert_eq!(client.get_received_total(&owner), 0);
+
+    // Totals are kept per recipient outside instance storage
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("RECEIVED")));
+    });
+
+    // A reset starts every recipient from zero again
+    let admin = Address::generate(&env);
+    client.set_upgrade_admin(&owner, &admin);
+    client.set_reset_enabled(&admin, &true);
+    client.reset(&admin);
+    assert_eq!(client.get_received_total(&accounts.savings), 0);
+}
+
+// Mock sub-contracts for get_net_position: each reports a per-owner total, 0 when unset
+mod mock_savings {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockSavings;
+
+    #[contractimpl]
+    impl MockSavings {
+        pub fn set_total(env: Env, owner: Address, amount: i128) {
+            env.storage().instance().set(&owner, &amount);
+        }
+        pub fn get_total_saved(env: Env, owner: Address) -> i128 {
+            env.storage().instance().get(&owner).unwrap_or(0)
+        }
+    }
+}
+
+mod mock_bills {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockBills;
+
+    #[contractimpl]
+    impl MockBills {
+        pub fn set_total(env: Env, owner: Address, amount: i128) {
+            env.storage().instance().set(&owner, &amount);
+        }
+        pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
+            env.storage().instance().get(&owner).unwrap_or(0)
+        }
+    }
+}
+
+mod mock_insurance {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockInsurance;
+
+    #[contractimpl]
+    impl MockInsurance {
+        pub fn set_total(env: Env, owner: Address, amount: i128) {
+            env.storage().instance().set(&owner, &amount);
+        }
+        pub fn get_total_monthly_premium(env: Env, owner: Address) -> i128 {
+            env.storage().instance().get(&owner).unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_get_net_position_across_contracts() {
+    use mock_bills::{MockBills, MockBillsClient};
+    use mock_insurance::{MockInsurance, MockInsuranceClient};
+    use mock_savings::{MockSavings, MockSavingsClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let savings_addr = env.register_contract(None, MockSavings);
+    let bills_addr = env.register_contract(None, MockBills);
+    let insurance_addr = env.register_contract(None, MockInsurance);
+    let owner = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+
+    MockSavingsClient::new(&env, &savings_addr).set_total(&owner, &5_000);
+    MockBillsClient::new(&env, &bills_addr).set_total(&owner, &1_200);
+    MockInsuranceClient::new(&env, &insurance_addr).set_total(&owner, &300);
+
+    let net = client.get_net_position(&owner, &savings_addr, &insurance_addr, &bills_addr);
+    assert_eq!(net, 5_000 - 1_200 - 300);
+
+    // Obligations without savings go negative
+    MockSavingsClient::new(&env, &savings_addr).set_total(&owner, &0);
+    let net = client.get_net_position(&owner, &savings_addr, &insurance_addr, &bills_addr);
+    assert_eq!(net, -1_500);
+
+    // Nothing recorded anywhere
+    let net = client.get_net_position(&newcomer, &savings_addr, &insurance_addr, &bills_addr);
+    assert_eq!(net, 0);
+}
+
+#[test]
+fn test_get_split_allocations_rounded_to_whole_units() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &33, &33, &33, &1);
+    // One whole USDC with 7 decimals
+    let unit = 10_000_000i128;
+    let total = 10 * unit;
+
+    // Raw split is 3.3 / 3.3 / 3.3 / 0.1 USDC
+    let events_before = client.get_event_seq();
+    let published_before = env.events().all().len();
+    let allocations = client.get_split_allocations_rounded(&total, &unit);
+    assert_eq!(env.events().all().len(), published_before);
+    assert_eq!(client.get_event_seq(), events_before);
+    assert_eq!(allocations.len(), 4);
+    let mut sum = 0i128;
+    for allocation in allocations.iter() {
+        assert_eq!(allocation.amount % unit, 0);
+        sum += allocation.amount;
+    }
+    assert_eq!(sum, total);
+    assert_eq!(allocations.get(0).unwrap().amount, 3 * unit);
+    assert_eq!(
+        allocations.get(3).unwrap().category,
+        symbol_short!("INSURANCE")
+    );
+    assert_eq!(allocations.get(3).unwrap().amount, unit);
+
+    let result = client.try_get_split_allocations_rounded(&total, &0);
+    assert!(matches!(
+        result,
+        Err(Ok(RemittanceSplitError::InvalidAmount))
+    ));
+}
+
+#[test]
+fn test_min_category_amount_borrows_from_largest() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    client.set_min_category_amount(&owner, &1, &10);
+    assert_eq!(client.get_min_category_amount(), 10);
+
+    // 100 splits as [50, 30, 15, 5]; insurance borrows 5 from spending
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &2, &accounts, &100, &false)
+        .is_some());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 45);
+    assert_eq!(token_client.balance(&accounts.savings), 30);
+    assert_eq!(token_client.balance(&accounts.bills), 15);
+    assert_eq!(token_client.balance(&accounts.insurance), 10);
+
+    // 39 can't give four categories 10 each
+    let result = client.try_distribute_usdc(&token.address(), &owner, &3, &accounts, &39, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::BelowCategoryMinimum)));
+}
+
+#[test]
+fn test_dynamic_split_with_six_categories() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let mut categories = Vec::new(&env);
+    categories.push_back((symbol_short!("RENT"), 35));
+    categories.push_back((symbol_short!("SCHOOL"), 20));
+    categories.push_back((symbol_short!("MEDICAL"), 15));
+    categories.push_back((symbol_short!("FOOD"), 15));
+    categories.push_back((symbol_short!("SAVINGS"), 10));
+    categories.push_back((symbol_short!("OTHER"), 5));
+    assert!(client.initialize_split_dynamic(&owner, &0, &categories));
+    assert_eq!(client.get_split_categories(), categories);
+
+    // 35% of 999 floors to 349; the last bucket absorbs the rounding
+    let allocations = client.calculate_split_dynamic(&999);
+    assert_eq!(allocations.len(), 6);
+    let mut total = 0;
+    for allocation in allocations.iter() {
+        total += allocation.amount;
+    }
+    assert_eq!(total, 999);
+    assert_eq!(allocations.get(0).unwrap().amount, 349);
+    assert_eq!(allocations.get(5).unwrap().category, symbol_short!("OTHER"));
+    assert_eq!(
+        allocations.get(5).unwrap().amount,
+        999 - 349 - 199 - 149 - 149 - 99
+    );
+
+    // The four-bucket API doesn't apply to a six-category split
+    let result = client.try_calculate_split(&999);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidCategory)));
+}
+
+#[test]
+fn test_dynamic_split_validation_and_four_field_delegation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let mut categories = Vec::new(&env);
+    categories.push_back((symbol_short!("RENT"), 60));
+    categories.push_back((symbol_short!("FOOD"), 30));
+    let result = client.try_initialize_split_dynamic(&owner, &0, &categories);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    categories.push_back((symbol_short!("RENT"), 10));
+    let result = client.try_initialize_split_dynamic(&owner, &0, &categories);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidCategory)));
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let allocations = client.calculate_split_dynamic(&1000);
+    assert_eq!(allocations.len(), 4);
+    assert_eq!(
+        allocations.get(0).unwrap().category,
+        symbol_short!("SPENDING")
+    );
+    assert_eq!(
+        allocations.get(3).unwrap().category,
+        symbol_short!("INSURANCE")
+    );
+    assert_eq!(allocations.get(3).unwrap().amount, 50);
+    assert_eq!(
+        client.calculate_split(&1000),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+    assert!(client.verify_integrity());
+}
+
+#[test]
+fn test_distribute_usdc_takes_platform_fee_first() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result =
+        client.try_initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &10_001, &collector);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFeeBps)));
+    client.initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &250, &collector);
+    let config = client.get_config().unwrap();
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.fee_collector, Some(collector.clone()));
+
+    // 2.5% of 1001 floors to 25; the remaining 976 is split and insurance absorbs rounding
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &1, &accounts, &1_001, &false)
+        .is_some());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&collector), 25);
+    assert_eq!(token_client.balance(&accounts.spending), 488);
+    assert_eq!(token_client.balance(&accounts.savings), 292);
+    assert_eq!(token_client.balance(&accounts.bills), 146);
+    assert_eq!(token_client.balance(&accounts.insurance), 50);
+    assert_eq!(token_client.balance(&owner), 10_000 - 1_001);
+
+    // A collector that can't receive is caught before anything moves
+    token
+        .issuer()
+        .set_flag(soroban_sdk::testutils::IssuerFlags::RevocableFlag);
+    StellarAssetClient::new(&env, &token.address()).set_authorized(&collector, &false);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &2, &accounts, &1_001, &false)
+        .is_none());
+    assert_eq!(token_client.balance(&owner), 10_000 - 1_001);
+    assert_eq!(client.get_nonce(&owner), 2);
+}
+
+#[test]
+fn test_import_snapshot_checks_platform_fee() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &250, &collector);
+    let snapshot = client.export_snapshot(&owner).unwrap();
+
+    // The fee settings are covered by the checksum
+    let mut redirected = snapshot.clone();
+    redirected.config.fee_collector = Some(Address::generate(&env));
+    let result = client.try_import_snapshot(&owner, &1, &redirected);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ChecksumMismatch)));
+
+    // and an out-of-range fee is refused even with a matching checksum
+    let mut excessive = snapshot.clone();
+    excessive.config.fee_bps = 10_001;
+    excessive.checksum =
+        RemittanceSplit::compute_checksum(&env, 3, CHECKSUM_ALGO_FNV, &excessive.config).unwrap();
+    let result = client.try_import_snapshot(&owner, &1, &excessive);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFeeBps)));
+
+    assert!(client.import_snapshot(&owner, &1, &snapshot));
+    assert_eq!(client.get_config().unwrap().fee_bps, 250);
+}
+
+#[test]
+fn test_cancel_schedule_makes_execute_a_no_op() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let schedule_id = client.schedule_distribution(
+        &owner,
+        &1,
+        &token.address(),
+        &accounts,
+        &1_000,
+        &2_000,
+        &86_400,
+    );
+    assert_eq!(client.get_schedule(&schedule_id).unwrap().next_due, 2_000);
+    assert!(!client.execute_scheduled(&schedule_id));
+
+    set_time(&env, 2_000);
+    assert!(client.execute_scheduled(&schedule_id));
+    let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.next_due, 2_000 + 86_400);
+    assert_eq!(schedule.last_executed, Some(2_000));
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 500);
+    assert_eq!(client.get_daily_volume(&0), 1_000);
+
+    client.cancel_schedule(&owner, &2, &schedule_id);
+    assert!(client.get_schedule(&schedule_id).is_none());
+    assert!(!client.get_remittance_schedule(&schedule_id).unwrap().active);
+    let last = client.get_audit_log(&0, &100).last().unwrap();
+    assert_eq!(last.operation, symbol_short!("sch_cncl"));
+    assert!(last.success);
+
+    set_time(&env, 2_000 + 86_400);
+    assert!(!client.execute_scheduled(&schedule_id));
+    assert_eq!(token_client.balance(&accounts.spending), 500);
+
+    let result = client.try_cancel_schedule(&owner, &3, &schedule_id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ScheduleNotFound)));
+}
+
+#[test]
+fn test_execute_scheduled_uses_the_distribute_usdc_pipeline() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    client.initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &250, &collector);
+    client.set_min_transfer(&owner, &1, &100);
+    client.set_daily_cap(&owner, &2, &1_500);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let schedule_id = client.schedule_distribution(
+        &owner,
+        &3,
+        &token.address(),
+        &accounts,
+        &1_000,
+        &2_000,
+        &3_600,
+    );
+
+    // Fee first, then insurance's 50 of the 975 net folds into spending
+    set_time(&env, 2_000);
+    let preview = client.preview_distribution(&token.address(), &accounts, &1_000);
+    assert!(client.execute_scheduled(&schedule_id));
+    let token_client = TokenClient::new(&env, &token.address());
+    for (recipient, _, projected) in preview.iter() {
+        assert_eq!(token_client.balance(&recipient), projected);
+    }
+    assert_eq!(token_client.balance(&collector), 25);
+
+    // The next run would breach the daily cap, so nothing moves
+    set_time(&env, 2_000 + 3_600);
+    assert!(!client.execute_scheduled(&schedule_id));
+    assert_eq!(token_client.balance(&owner), 9_000);
+
+    // Recipients locked in after scheduling are enforced at execution time
+    set_time(&env, 2_000 + 86_400);
+    let locked = AccountGroup {
+        spending: Address::generate(&env),
+        ..accounts.clone()
+    };
+    client.set_accounts(&owner, &4, &locked);
+    let result = client.try_execute_scheduled(&schedule_id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::RecipientsLocked)));
+    assert_eq!(token_client.balance(&owner), 9_000);
+}
+
+#[test]
+fn test_bps_split_supports_fractional_percentages() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let result = client.try_initialize_split_bps(&owner, &0, &1250, &3750, &2500, &2400);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    // 12.5% / 37.5% / 25% / 25%
+    client.initialize_split_bps(&owner, &0, &1250, &3750, &2500, &2500);
+    assert!(client.is_bps_mode());
+    assert_eq!(
+        client.calculate_split(&1_000),
+        Vec::from_array(&env, [125, 375, 250, 250])
+    );
+    assert_eq!(
+        client.get_split_bps(),
+        Vec::from_array(&env, [1250, 3750, 2500, 2500])
+    );
+
+    // update_split takes basis points in this mode
+    let result = client.try_update_split(&owner, &1, &50, &30, &15, &5);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+    assert!(client.update_split(&owner, &1, &5000, &3000, &1500, &500));
+    assert_eq!(
+        client.calculate_split(&999),
+        Vec::from_array(&env, [499, 299, 149, 52])
+    );
+}
+
+#[test]
+fn test_import_upgrades_legacy_snapshot_versions() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &40, &40, &10, &10);
+    assert!(!client.is_bps_mode());
+
+    // Exports are version 3 in basis points even from a whole-percent config
+    let latest = client.export_snapshot(&owner).unwrap();
+    assert_eq!(latest.version, 3);
+    assert_eq!(latest.config.spending_percent, 4000);
+
+    // A version 3 snapshot can't be downgraded to a legacy checksum
+    let mut downgraded = latest.clone();
+    downgraded.checksum_algo = CHECKSUM_ALGO_ROLLING;
+    let result = client.try_import_snapshot(&owner, &1, &downgraded);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ChecksumOutdated)));
+    for version in [0, 4] {
+        let mut unknown = latest.clone();
+        unknown.version = version;
+        let result = client.try_import_snapshot(&owner, &1, &unknown);
+        assert_eq!(result, Err(Ok(RemittanceSplitError::UnsupportedVersion)));
+    }
+
+    // Version 1 carried whole percentages and an additive checksum: sum * 31
+    let mut v1 = latest.clone();
+    v1.version = 1;
+    v1.checksum_algo = CHECKSUM_ALGO_ADDITIVE;
+    v1.config.spending_percent = 40;
+    v1.config.savings_percent = 40;
+    v1.config.bills_percent = 10;
+    v1.config.insurance_percent = 10;
+    v1.checksum = (1 + 40 + 40 + 10 + 10) * 31;
+    assert!(client.import_snapshot(&owner, &1, &v1));
+    assert_eq!(client.get_config().unwrap().spending_percent, 4000);
+
+    // Version 2 was already in basis points, signed with the rolling checksum
+    let mut v2 = latest.clone();
+    v2.version = 2;
+    v2.checksum_algo = CHECKSUM_ALGO_ROLLING;
+    v2.checksum = [2u64, 4000, 4000, 1000, 1000]
+        .iter()
+        .fold(17u64, |acc, f| acc.wrapping_mul(31).wrapping_add(*f));
+    assert!(client.import_snapshot(&owner, &2, &v2));
+
+    assert!(client.import_snapshot(&owner, &3, &latest));
+    assert!(client.is_bps_mode());
+    assert!(client.verify_integrity());
+    assert_eq!(
+        client.calculate_split(&1_000),
+        Vec::from_array(&env, [400, 400, 100, 100])
+    );
+}
+
+#[test]
+fn test_transfer_ownership() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let result = client.try_transfer_ownership(&owner, &0, &new_owner);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let result = client.try_transfer_ownership(&new_owner, &0, &owner);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    assert!(client.transfer_ownership(&owner, &1, &new_owner));
+    assert_eq!(client.get_config().unwrap().owner, new_owner);
+    assert_eq!(client.get_nonce(&owner), 2);
+    assert_eq!(client.get_nonce(&new_owner), 0);
+    let last = client.get_audit_log(&0, &100).last().unwrap();
+    assert_eq!(last.operation, symbol_short!("xferown"));
+    assert!(last.success);
+
+    // Only the new owner can change the split now
+    let result = client.try_update_split(&owner, &2, &40, &40, &10, &10);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    assert!(client.update_split(&new_owner, &0, &40, &40, &10, &10));
+}
+
+#[test]
+fn test_pause_blocks_mutations_but_not_reads() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let snapshot = client.export_snapshot(&owner).unwrap();
+
+    let result = client.try_pause(&other, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    client.pause(&owner, &1);
+    assert!(client.is_paused());
+
+    let result = client.try_update_split(&owner, &2, &40, &40, &10, &10);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ContractPaused)));
+    let result =
+        client.try_distribute_usdc(&token.address(), &owner, &2, &accounts, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ContractPaused)));
+    let result = client.try_import_snapshot(&owner, &2, &snapshot);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ContractPaused)));
+
+    // Reads keep working
+    assert_eq!(client.get_split(), Vec::from_array(&env, [50, 30, 15, 5]));
+    let last = client.get_audit_log(&0, &100).last().unwrap();
+    assert_eq!(last.operation, symbol_short!("pause"));
+    assert!(last.success);
+
+    client.unpause(&owner, &2);
+    assert!(!client.is_paused());
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &3, &accounts, &1_000, &false)
+        .is_some());
+}
+
+#[test]
+fn test_audit_log_keeps_entries_across_chunks() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    for nonce in 1..150 {
+        client.set_min_category_amount(&owner, &nonce, &(nonce as i128));
+    }
+    assert_eq!(client.get_audit_count(), 150);
+
+    let first_page = client.get_audit_log(&0, &500);
+    assert_eq!(first_page.len(), 100);
+    let first = first_page.get(0).unwrap();
+    assert_eq!(first.operation, symbol_short!("init"));
+    assert!(first.success);
+
+    // A page straddling the chunk boundary
+    let middle = client.get_audit_log(&95, &10);
+    assert_eq!(middle.len(), 10);
+    assert!(middle
+        .iter()
+        .all(|e| e.operation == symbol_short!("set_min") && e.success));
+
+    assert_eq!(client.get_audit_log(&100, &100).len(), 50);
+    assert_eq!(client.get_audit_log(&150, &100).len(), 0);
+}
+
+#[test]
+fn test_calculate_split_by_weights() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+
+    let amounts = client.calculate_split_by_weights(&1000, &vec![&env, 2, 1, 1]);
+    assert_eq!(amounts, vec![&env, 500, 250, 250]);
+    assert_eq!(amounts.iter().sum::<i128>(), 1000);
+
+    // 1000 / 3 leaves a remainder of 1, which goes to the largest weight
+    let amounts = client.calculate_split_by_weights(&1000, &vec![&env, 1, 1, 1]);
+    assert_eq!(amounts, vec![&env, 334, 333, 333]);
+    let amounts = client.calculate_split_by_weights(&1000, &vec![&env, 1, 3, 2]);
+    assert_eq!(amounts, vec![&env, 166, 501, 333]);
+
+    assert_eq!(
+        client.try_calculate_split_by_weights(&1000, &Vec::new(&env)),
+        Err(Ok(RemittanceSplitError::InvalidWeights))
+    );
+    assert_eq!(
+        client.try_calculate_split_by_weights(&1000, &vec![&env, 0, 0]),
+        Err(Ok(RemittanceSplitError::InvalidWeights))
+    );
+}
+
+#[test]
+fn test_distribute_usdc_returns_receipt() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.mock_all_auths();
+    set_time(&env, 5_000);
+
+    client.initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &250, &collector);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    assert_eq!(
+        client.distribute_usdc(&token.address(), &owner, &1, &accounts, &0, &false),
+        None
+    );
+    // Even the rejected zero amount is audited under `owner`, so it must be signed
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths().first().unwrap().0, owner);
+
+    let receipt = client
+        .distribute_usdc(&token.address(), &owner, &1, &accounts, &1_001, &false)
+        .unwrap();
+    assert_eq!(receipt.total_amount, 1_001);
+    assert_eq!(receipt.token, token.address());
+    assert_eq!(receipt.timestamp, 5_000);
+    let expected = [
+        (symbol_short!("SPENDING"), 488),
+        (symbol_short!("SAVINGS"), 292),
+        (symbol_short!("BILLS"), 146),
+        (symbol_short!("INSURANCE"), 50),
+        (symbol_short!("FEE"), 25),
+    ];
+    assert_eq!(receipt.allocations.len(), expected.len() as u32);
+    for (allocation, (category, amount)) in receipt.allocations.iter().zip(expected) {
+        assert_eq!(allocation.category, category);
+        assert_eq!(allocation.amount, amount);
+    }
+    let moved: i128 = receipt.allocations.iter().map(|a| a.amount).sum();
+    assert_eq!(moved, receipt.total_amount);
+}
+
+#[test]
+fn test_distribute_usdc_folds_dust_into_spending() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    assert_eq!(
+        client.try_set_min_transfer(&owner, &1, &-1),
+        Err(Ok(RemittanceSplitError::InvalidAmount))
+    );
+    client.set_min_transfer(&owner, &1, &10);
+    assert_eq!(client.get_config().unwrap().min_transfer, 10);
+
+    // 40 splits 20/12/6/2: bills and insurance are below 10 and go to spending
+    let receipt = client
+        .distribute_usdc(&token.address(), &owner, &2, &accounts, &40, &false)
+        .unwrap();
+    let mut amounts = Vec::new(&env);
+    for allocation in receipt.allocations.iter() {
+        amounts.push_back(allocation.amount);
+    }
+    assert_eq!(amounts, vec![&env, 28, 12, 0, 0]);
+    assert_eq!(amounts.iter().sum::<i128>(), 40);
+
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 28);
+    assert_eq!(token_client.balance(&accounts.savings), 12);
+    assert_eq!(token_client.balance(&accounts.bills), 0);
+    assert_eq!(token_client.balance(&accounts.insurance), 0);
+}
+
+#[test]
+fn test_distribute_multi_splits_each_token_atomically() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let eurc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&owner, &10_000);
+    let eurc_asset = StellarAssetClient::new(&env, &eurc.address());
+    eurc_asset.mint(&owner, &10_000);
+    eurc.issuer()
+        .set_flag(soroban_sdk::testutils::IssuerFlags::RevocableFlag);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let transfers = vec![
+        &env,
+        (usdc.address(), 1_000i128),
+        (eurc.address(), 2_000i128),
+    ];
+
+    assert!(client.distribute_multi(&owner, &1, &accounts, &transfers));
+    let calculated = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == contract_id
+                && topics.len() == 2
+                && SplitEvent::try_from_val(&env, &topics.get(1).unwrap())
+                    .is_ok_and(|event| event == SplitEvent::Calculated)
+        })
+        .count();
+    assert_eq!(calculated, 2);
+    assert_eq!(client.get_nonce(&owner), 2);
+
+    let usdc_client = TokenClient::new(&env, &usdc.address());
+    let eurc_client = TokenClient::new(&env, &eurc.address());
+    assert_eq!(usdc_client.balance(&accounts.spending), 500);
+    assert_eq!(usdc_client.balance(&accounts.insurance), 50);
+    assert_eq!(eurc_client.balance(&accounts.spending), 1_000);
+    assert_eq!(eurc_client.balance(&accounts.insurance), 100);
+
+    // A recipient that can't take the second token rolls back the first one too
+    eurc_asset.set_authorized(&accounts.bills, &false);
+    let result = client.try_distribute_multi(&owner, &2, &accounts, &transfers);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::RecipientCannotReceive))
+    );
+    assert_eq!(usdc_client.balance(&accounts.spending), 500);
+    assert_eq!(usdc_client.balance(&owner), 9_000);
+    assert_eq!(client.get_nonce(&owner), 2);
+
+    let result = client.try_distribute_multi(&owner, &2, &accounts, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+}
+
+#[test]
+fn test_preview_distribution_projects_balances_without_moving_funds() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let asset = StellarAssetClient::new(&env, &token.address());
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    asset.mint(&accounts.savings, &700);
+    let events_before = client.get_event_seq();
+
+    let preview = client.preview_distribution(&token.address(), &accounts, &1_000);
+    assert_eq!(
+        preview,
+        vec![
+            &env,
+            (accounts.spending.clone(), 500, 500),
+            (accounts.savings.clone(), 300, 1_000),
+            (accounts.bills.clone(), 150, 150),
+            (accounts.insurance.clone(), 50, 50),
+        ]
+    );
+    assert!(env.auths().is_empty());
+    assert_eq!(client.get_event_seq(), events_before);
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&accounts.spending),
+        0
+    );
+
+    let result = client.try_preview_distribution(&token.address(), &accounts, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+}
+
+#[test]
+fn test_preview_distribution_matches_distribute_usdc_with_fee_and_dust() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split_with_fee(&owner, &0, &50, &30, &15, &5, &250, &collector);
+    client.set_min_transfer(&owner, &1, &100);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    // 25 fee, then 975 split 487/292/146/50; insurance is dust folded into spending
+    let preview = client.preview_distribution(&token.address(), &accounts, &1_000);
+    assert_eq!(
+        preview,
+        vec![
+            &env,
+            (accounts.spending.clone(), 537, 537),
+            (accounts.savings.clone(), 292, 292),
+            (accounts.bills.clone(), 146, 146),
+            (accounts.insurance.clone(), 0, 0),
+            (collector.clone(), 25, 25),
+        ]
+    );
+
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1_000, &false);
+    let token_client = TokenClient::new(&env, &token.address());
+    for (recipient, _, projected) in preview.iter() {
+        assert_eq!(token_client.balance(&recipient), projected);
+    }
+}
+
+#[test]
+fn test_daily_cap_blocks_excess_until_window_rolls() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+    set_time(&env, 10_000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    // Without a cap nothing is tracked
+    client.distribute_once(
+        &owner,
+        &1,
+        &token.address(),
+        &accounts,
+        &100,
+        &25,
+        &25,
+        &25,
+        &25,
+    );
+    assert_eq!(client.get_distributed_today(), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("DAY_LOG")));
+    });
+
+    client.set_daily_cap(&owner, &2, &1_500);
+    assert_eq!(client.get_daily_cap(), 1_500);
+
+    // Under the cap
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &3, &accounts, &1_000, &false)
+        .is_some());
+    set_time(&env, 50_000);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &4, &accounts, &500, &false)
+        .is_some());
+    assert_eq!(client.get_distributed_today(), 1_500);
+
+    // Breaching it moves nothing and leaves a failed audit entry
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &5, &accounts, &1, &false)
+        .is_none());
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&owner), 8_400);
+    let log = client.get_audit_log(&0, &100);
+    let last = log.last().unwrap();
+    assert_eq!(last.operation, symbol_short!("distrib"));
+    assert!(!last.success);
+    assert_eq!(client.get_nonce(&owner), 5);
+
+    // The window rolls hourly: a day after the end of 10_000's hour (7_200..10_800)
+    // only the 500 sent at 50_000 still counts
+    set_time(&env, 10_799 + 86_400);
+    assert_eq!(client.get_distributed_today(), 1_500);
+    set_time(&env, 10_800 + 86_400);
+    assert_eq!(client.get_distributed_today(), 500);
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &5, &accounts, &1_200, &false)
+        .is_none());
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &5, &accounts, &1_000, &false)
+        .is_some());
+    assert_eq!(client.get_distributed_today(), 1_500);
+    set_time(&env, 50_400 + 86_400);
+    assert_eq!(client.get_distributed_today(), 1_000);
+
+    // distribute_once is held to the same cap
+    let result = client.try_distribute_once(
+        &owner,
+        &6,
+        &token.address(),
+        &accounts,
+        &501,
+        &25,
+        &25,
+        &25,
+        &25,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::DailyCapExceeded)));
+    assert!(client.distribute_once(
+        &owner,
+        &6,
+        &token.address(),
+        &accounts,
+        &500,
+        &25,
+        &25,
+        &25,
+        &25
+    ));
+    assert_eq!(client.get_distributed_today(), 1_500);
+
+    // Another sender's distribute_once neither hits nor uses up the owner's cap
+    let stranger = Address::generate(&env);
+    StellarAssetClient::new(&env, &token.address()).mint(&stranger, &1_000);
+    assert!(client.distribute_once(
+        &stranger,
+        &0,
+        &token.address(),
+        &accounts,
+        &1_000,
+        &25,
+        &25,
+        &25,
+        &25
+    ));
+    assert_eq!(client.get_distributed_today(), 1_500);
+}
+
+#[test]
+fn test_reset_config_allows_fresh_initialization() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let wrong_owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&wrong_owner, &0, &50, &30, &15, &5);
+    let result = client.try_reset_config(&stranger, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    assert!(client.reset_config(&wrong_owner, &1));
+    assert!(client.get_config().is_none());
+    let last_event = env.events().all().last().unwrap();
+    let topic: SplitEvent = SplitEvent::try_from_val(&env, &last_event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic, SplitEvent::ConfigReset);
+    let log = client.get_audit_log(&0, &100);
+    assert_eq!(log.last().unwrap().operation, symbol_short!("rst_cfg"));
+
+    client.initialize_split(&owner, &0, &40, &40, &10, &10);
+    let config = client.get_config().unwrap();
+    assert_eq!(config.owner, owner);
+    assert_eq!(client.get_split(), vec![&env, 40, 40, 10, 10]);
+}
+
+#[test]
+fn test_distribute_usdc_stored_uses_locked_accounts() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &10_000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result = client.try_distribute_usdc_stored(&token.address(), &owner, &1, &1_000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::AccountsNotSet)));
+
+    client.set_accounts(&owner, &1, &accounts);
+    assert_eq!(client.get_accounts(), Some(accounts.clone()));
+
+    let receipt = client
+        .distribute_usdc_stored(&token.address(), &owner, &2, &1_000)
+        .unwrap();
+    assert_eq!(receipt.total_amount, 1_000);
+    let token_client = TokenClient::new(&env, &token.address());
+    assert_eq!(token_client.balance(&accounts.spending), 500);
+    assert_eq!(token_client.balance(&accounts.insurance), 50);
+
+    // Substituting a recipient is rejected once the accounts are locked in
+    let redirected = AccountGroup {
+        spending: Address::generate(&env),
+        ..accounts.clone()
+    };
+    let result =
+        client.try_distribute_usdc(&token.address(), &owner, &3, &redirected, &1_000, &false);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::RecipientsLocked)));
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &3, &accounts, &1_000, &false)
+        .is_some());
+
+    // Every other entry point enforces the lock too
+    let transfers = vec![&env, (token.address(), 1_000i128)];
+    let result = client.try_distribute_multi(&owner, &4, &redirected, &transfers);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::RecipientsLocked)));
+    let result = client.try_distribute_once(
+        &owner,
+        &4,
+        &token.address(),
+        &redirected,
+        &1_000,
+        &25,
+        &25,
+        &25,
+        &25,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::RecipientsLocked)));
+    let result = client.try_schedule_distribution(
+        &owner,
+        &4,
+        &token.address(),
+        &redirected,
+        &1_000,
+        &(env.ledger().timestamp() + 60),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::RecipientsLocked)));
+    assert_eq!(token_client.balance(&redirected.spending), 0);
+}
+
+#[test]
+fn test_diff_config_reports_changed_categories() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.update_split(&owner, &1, &50, &20, &25, &5);
+    assert_eq!(client.get_config_history().len(), 2);
+
+    assert_eq!(
+        client.diff_config(&0, &1),
+        vec![&env, symbol_short!("SAVINGS"), symbol_short!("BILLS")]
+    );
+    assert_eq!(client.diff_config(&1, &1), Vec::<Symbol>::new(&env));
+    assert_eq!(
+        client.try_diff_config(&0, &2),
+        Err(Ok(RemittanceSplitError::InvalidHistoryIndex))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate recipient address")]
+fn test_distribute_usdc_rejects_duplicate_recipients() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1_000);
+    let shared = Address::generate(&env);
+    let accounts = AccountGroup {
+        spending: shared.clone(),
+        savings: shared,
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1_000, &false);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate recipient address")]
+fn test_preview_distribution_rejects_duplicate_recipients() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    let shared = Address::generate(&env);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: shared.clone(),
+        insurance: shared,
+    };
+
+    client.preview_distribution(&token.address(), &accounts, &1_000);
+}
+
+#[test]
+fn test_distribute_usdc_distinct_or_allowed_duplicate_recipients() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &2_000);
+    let distinct = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    assert_eq!(
+        client
+            .preview_distribution(&token.address(), &distinct, &1_000)
+            .len(),
+        4
+    );
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &1, &distinct, &1_000, &false)
+        .is_some());
+
+    // Once the owner opts in, a shared address receives both categories
+    client.set_allow_duplicate_recipients(&owner, &2, &true);
+    let shared = Address::generate(&env);
+    let duplicated = AccountGroup {
+        spending: shared.clone(),
+        savings: shared.clone(),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    assert!(client
+        .distribute_usdc(&token.address(), &owner, &3, &duplicated, &1_000, &false)
+        .is_some());
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&shared),
+        800
+    );
+}
@@ -65,9 +65,10 @@ fn bench_distribute_usdc_worst_case() {
             &nonce,
             &accounts,
             &amount,
+            &true,
         )
     });
-    assert!(distributed);
+    assert!(distributed.is_some());
 
     println!(
         r#"{{"contract":"remittance_split","method":"distribute_usdc","scenario":"4_recipients_all_nonzero","cpu":{},"mem":{}}}"#,
@@ -117,10 +117,17 @@ The pagination API is designed to minimize gas costs:
 
 */
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, Address, Env, Map, String, Symbol, Vec,
 };
 
+/// Bill Payments calls made by `withdraw_to_pay_bill`
+#[contractclient(name = "BillPaymentsClient")]
+pub trait BillPaymentsTrait {
+    fn get_amounts_owed(env: Env, owner: Address, now_ts: u64) -> Vec<(u32, i128)>;
+    fn pay_bill_usdc(env: Env, caller: Address, bill_id: u32, usdc_contract: Address) -> i128;
+}
+
 // Event topics
 const GOAL_CREATED: Symbol = symbol_short!("created");
 const FUNDS_ADDED: Symbol = symbol_short!("added");
@@ -183,12 +190,35 @@ pub enum SavingsGoalError {
     NextDueDateMustBeInFuture = 18,
     ScheduleNotFound = 19,
     ArithmeticError = 20,
+    OwnerFrozen = 21,
+    AtomicFundingIncomplete = 22,
+    CannotMergeIntoSelf = 23,
+    InvalidStretchTarget = 24,
+    TargetDateMustBeInFuture = 25,
+    GoalNotComplete = 26,
+    DestinationNotAllowed = 27,
+    TemplateNotFound = 28,
+    /// The bill is not an unpaid bill of the caller
+    BillNotPayable = 29,
+    /// The withdrawal is smaller than the amount owed on the bill
+    InsufficientForBill = 30,
+    InvalidRollIncrement = 31,
 }
 
 /// Savings goal data structure with owner tracking for access control
 #[contract]
 pub struct SavingsGoalContract;
 
+/// Outcome of crediting a deposit with `apply_deposit`
+struct Deposit {
+    new_total: i128,
+    previously_completed: bool,
+    was_completed: bool,
+    auto_locked: bool,
+    rolled: bool,
+    stretch_crossed: bool,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsGoal {
@@ -200,6 +230,50 @@ pub struct SavingsGoal {
     pub target_date: u64,
     pub locked: bool,
     pub unlock_date: Option<u64>,
+    /// Amount that may be withdrawn while the goal stays locked
+    pub withdrawable_amount: i128,
+    /// Ledger timestamp of the last change to this goal
+    pub updated_at: u64,
+    /// Ledger timestamp when the goal was created
+    pub created_at: u64,
+    /// Lifetime deposits into the goal (withdrawals and interest not subtracted/added)
+    pub total_deposited: i128,
+    /// Lock the goal automatically when a deposit reaches the target
+    pub lock_on_complete: bool,
+    /// Currency the owner wants amounts shown in (display only)
+    pub display_currency: Option<Symbol>,
+    /// Display units per token unit, in basis points (10000 = 1:1)
+    pub rate_to_token_bps: u32,
+    /// Higher values are funded first by `allocate_savings_by_priority`
+    pub priority: u32,
+    /// Only accept deposits that complete the goal in a single call
+    pub atomic_funding: bool,
+    /// Interest credited to this goal over its lifetime
+    pub total_interest: i128,
+    /// Optional stretch goal above `target_amount` (0 when unset)
+    pub stretch_target: i128,
+    /// Free-form category tag, set by `create_goals_batch`
+    pub category: Option<Symbol>,
+    /// Ledger timestamp when `current_amount` first reached `target_amount`
+    pub completed_at: Option<u64>,
+    /// Reject withdrawals until `current_amount` reaches `target_amount`
+    pub withdraw_only_when_complete: bool,
+    /// Raw balance held per token via `add_to_goal_token`
+    pub token_balances: Map<Address, i128>,
+    /// Normalized value credited to `current_amount` per token; token withdrawals
+    /// release it pro rata, whatever the rate is now
+    pub token_basis: Map<Address, i128>,
+    /// Where `withdraw_to`/`close_account` may send funds; empty means anywhere
+    pub allowed_destinations: Vec<Address>,
+    /// Highest `current_amount` the goal has reached; withdrawals never lower it
+    pub high_water_mark: i128,
+    /// Lifetime cap on `total_interest` (0 = uncapped)
+    pub max_interest: i128,
+    /// Raise the target by `roll_increment` whenever a deposit reaches it
+    pub auto_roll: bool,
+    pub roll_increment: i128,
+    /// Times the target has been rolled forward
+    pub roll_count: u32,
 }
 
 /// Schedule for automatic savings deposits
@@ -219,6 +293,18 @@ pub struct SavingsSchedule {
     pub missed_count: u32,
 }
 
+/// Token-backed recurring contribution into a goal, pulled from the owner via allowance
+#[contracttype]
+#[derive(Clone)]
+pub struct ContributionSchedule {
+    pub goal_id: u32,
+    pub owner: Address,
+    pub amount: i128,
+    pub interval_secs: u64,
+    pub next_run: u64,
+    pub usdc_contract: Address,
+}
+
 /// Events emitted by the contract for audit trail
 #[contracttype]
 #[derive(Clone)]
@@ -234,6 +320,23 @@ pub enum SavingsEvent {
     ScheduleMissed,
     ScheduleModified,
     ScheduleCancelled,
+    WithdrawableSet,
+    GoalsMerged,
+    StretchReached,
+    GoalRolled,
+}
+
+/// Contract-wide goal outcomes, from `get_completion_stats`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionStats {
+    pub total_goals: u32,
+    /// Goals that have reached their target at some point
+    pub completed_goals: u32,
+    pub total_target: i128,
+    pub total_saved: i128,
+    /// `completed_goals / total_goals` in basis points (0 when there are no goals)
+    pub completion_rate_bps: u32,
 }
 
 /// Response structure for paginated goals queries
@@ -269,8 +372,12 @@ const SNAPSHOT_VERSION: u32 = 1;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+/// An unfinished goal due within this window is hinted as `FUND`
+const FUND_HINT_WINDOW_SECS: u64 = 30 * 86400;
 const DEFAULT_PAGE_LIMIT: u32 = 20;
 const MAX_PAGE_LIMIT: u32 = 100;
+/// Contribution timestamps kept per goal for `get_consistency`; oldest dropped first
+const MAX_CONTRIBUTION_HISTORY: u32 = 50;
 
 pub mod pause_functions {
     use soroban_sdk::{symbol_short, Symbol};
@@ -281,6 +388,14 @@ pub mod pause_functions {
     pub const UNLOCK: Symbol = symbol_short!("unlock");
 }
 
+/// Amount credited to one goal by allocate_savings_by_priority
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalAllocation {
+    pub goal_id: u32,
+    pub amount: i128,
+}
+
 /// Single contribution for batch_add_to_goals
 #[contracttype]
 #[derive(Clone)]
@@ -289,6 +404,27 @@ pub struct ContributionItem {
     pub amount: i128,
 }
 
+/// Single goal definition for create_goals_batch
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalInput {
+    pub name: String,
+    pub target_amount: i128,
+    pub target_date: u64,
+    pub category: Symbol,
+}
+
+/// Admin-defined savings product that owners can instantiate with `create_from_template`
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalTemplate {
+    pub key: Symbol,
+    pub name: String,
+    pub target_amount: i128,
+    /// Goal `target_date` is the creation time plus this many seconds
+    pub target_date_offset_secs: u64,
+}
+
 #[contractimpl]
 impl SavingsGoalContract {
     // Storage keys
@@ -338,6 +474,21 @@ impl SavingsGoalContract {
         Ok(())
     }
 
+    fn is_owner_frozen(env: &Env, owner: &Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, bool>>(&symbol_short!("FRZ_OWN"))
+            .unwrap_or_else(|| Map::new(env))
+            .get(owner.clone())
+            .unwrap_or(false)
+    }
+    fn require_owner_not_frozen(env: &Env, owner: &Address) -> Result<(), SavingsGoalError> {
+        if Self::is_owner_frozen(env, owner) {
+            return Err(SavingsGoalError::OwnerFrozen);
+        }
+        Ok(())
+    }
+
     pub fn set_pause_admin(
         env: Env,
         caller: Address,
@@ -430,6 +581,59 @@ impl SavingsGoalContract {
             .set(&symbol_short!("PAUSED_FN"), &m);
         Ok(())
     }
+    /// Freeze every goal of `owner` (fraud response). Blocks all of the owner's mutating
+    /// goal operations, and their scheduled deposits, until `admin_unfreeze_owner`.
+    pub fn admin_freeze_owner(
+        env: Env,
+        admin: Address,
+        owner: Address,
+    ) -> Result<(), SavingsGoalError> {
+        Self::set_owner_frozen(&env, &admin, &owner, true)?;
+        env.events()
+            .publish((symbol_short!("savings"), symbol_short!("own_frz")), owner);
+        Ok(())
+    }
+    pub fn admin_unfreeze_owner(
+        env: Env,
+        admin: Address,
+        owner: Address,
+    ) -> Result<(), SavingsGoalError> {
+        Self::set_owner_frozen(&env, &admin, &owner, false)?;
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("own_unfrz")),
+            owner,
+        );
+        Ok(())
+    }
+    pub fn is_owner_frozen_public(env: Env, owner: Address) -> bool {
+        Self::is_owner_frozen(&env, &owner)
+    }
+    fn set_owner_frozen(
+        env: &Env,
+        admin: &Address,
+        owner: &Address,
+        frozen: bool,
+    ) -> Result<(), SavingsGoalError> {
+        admin.require_auth();
+        let pause_admin = Self::get_pause_admin(env).ok_or(SavingsGoalError::Unauthorized)?;
+        if pause_admin != *admin {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        let mut frozen_owners: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FRZ_OWN"))
+            .unwrap_or_else(|| Map::new(env));
+        if frozen {
+            frozen_owners.set(owner.clone(), true);
+        } else {
+            frozen_owners.remove(owner.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FRZ_OWN"), &frozen_owners);
+        Ok(())
+    }
     pub fn is_paused(env: Env) -> bool {
         Self::get_global_paused(&env)
     }
@@ -508,6 +712,7 @@ impl SavingsGoalContract {
         // Access control: require owner authorization
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_GOAL)?;
+        Self::require_owner_not_frozen(&env, &owner)?;
 
         // Input validation
         if target_amount <= 0 {
@@ -531,16 +736,7 @@ impl SavingsGoalContract {
             .unwrap_or(0u32)
             + 1;
 
-        let goal = SavingsGoal {
-            id: next_id,
-            owner: owner.clone(),
-            name: name.clone(),
-            target_amount,
-            current_amount: 0,
-            target_date,
-            locked: true,
-            unlock_date: None,
-        };
+        let goal = Self::new_goal(&env, next_id, &owner, name, target_amount, target_date);
 
         goals.set(next_id, goal.clone());
         env.storage()
@@ -568,6 +764,236 @@ impl SavingsGoalContract {
         Ok(next_id)
     }
 
+    /// Create several goals for one owner in a single call. Every entry is validated
+    /// before any goal is created, so one invalid entry rejects the whole batch.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the goal owner (must authorize)
+    /// * `nonce` - Owner's transaction nonce (must equal get_nonce(owner))
+    /// * `goals` - Goal definitions (at most MAX_BATCH_SIZE)
+    ///
+    /// # Returns
+    /// The IDs of the created goals, in input order
+    ///
+    /// # Panics
+    /// - If the batch is too large
+    /// - If any target_amount is not positive
+    /// - If any target_date is not in the future
+    pub fn create_goals_batch(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        goals: Vec<GoalInput>,
+    ) -> Result<Vec<u32>, SavingsGoalError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_GOAL)?;
+        Self::require_owner_not_frozen(&env, &owner)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        if goals.len() > MAX_BATCH_SIZE {
+            return Err(SavingsGoalError::BatchTooLarge);
+        }
+        let now = env.ledger().timestamp();
+        for input in goals.iter() {
+            if input.target_amount <= 0 {
+                Self::append_audit(&env, symbol_short!("create"), &owner, false);
+                return Err(SavingsGoalError::TargetAmountMustBePositive);
+            }
+            if input.target_date <= now {
+                Self::append_audit(&env, symbol_short!("create"), &owner, false);
+                return Err(SavingsGoalError::TargetDateMustBeInFuture);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut stored: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+
+        let mut ids = Vec::new(&env);
+        for input in goals.iter() {
+            next_id += 1;
+            let mut goal = Self::new_goal(
+                &env,
+                next_id,
+                &owner,
+                input.name,
+                input.target_amount,
+                input.target_date,
+            );
+            goal.category = Some(input.category);
+            stored.set(next_id, goal.clone());
+            ids.push_back(next_id);
+
+            let event = GoalCreatedEvent {
+                goal_id: next_id,
+                name: goal.name,
+                target_amount: goal.target_amount,
+                target_date: goal.target_date,
+                timestamp: now,
+            };
+            env.events().publish((GOAL_CREATED,), event);
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCreated),
+                (next_id, owner.clone()),
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &stored);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+
+        Self::increment_nonce(&env, &owner);
+        Self::append_audit(&env, symbol_short!("create"), &owner, true);
+        Ok(ids)
+    }
+
+    /// Register or replace the goal template stored under `key` (pause admin only)
+    ///
+    /// # Panics
+    /// - If caller is not the pause admin
+    /// - If `target_amount` is not positive
+    /// - If `target_date_offset_secs` is zero
+    pub fn register_template(
+        env: Env,
+        admin: Address,
+        key: Symbol,
+        name: String,
+        target_amount: i128,
+        target_date_offset_secs: u64,
+    ) -> Result<(), SavingsGoalError> {
+        admin.require_auth();
+        let pause_admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if pause_admin != admin {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if target_amount <= 0 {
+            return Err(SavingsGoalError::TargetAmountMustBePositive);
+        }
+        if target_date_offset_secs == 0 {
+            return Err(SavingsGoalError::TargetDateMustBeInFuture);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut templates: Map<Symbol, GoalTemplate> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("TEMPLATES"))
+            .unwrap_or_else(|| Map::new(&env));
+        templates.set(
+            key.clone(),
+            GoalTemplate {
+                key,
+                name,
+                target_amount,
+                target_date_offset_secs,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("TEMPLATES"), &templates);
+        Ok(())
+    }
+
+    /// All registered goal templates
+    pub fn get_templates(env: Env) -> Vec<GoalTemplate> {
+        let templates: Map<Symbol, GoalTemplate> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("TEMPLATES"))
+            .unwrap_or_else(|| Map::new(&env));
+        templates.values()
+    }
+
+    /// Create a goal from the template under `key`, due `target_date_offset_secs` from
+    /// now and tagged with the template key as its category. Same checks and nonce
+    /// handling as `create_goals_batch`.
+    ///
+    /// # Returns
+    /// The new goal's ID
+    pub fn create_from_template(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        key: Symbol,
+    ) -> Result<u32, SavingsGoalError> {
+        let templates: Map<Symbol, GoalTemplate> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("TEMPLATES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let template = templates
+            .get(key.clone())
+            .ok_or(SavingsGoalError::TemplateNotFound)?;
+
+        let target_date = env
+            .ledger()
+            .timestamp()
+            .saturating_add(template.target_date_offset_secs);
+        let mut goals = Vec::new(&env);
+        goals.push_back(GoalInput {
+            name: template.name,
+            target_amount: template.target_amount,
+            target_date,
+            category: key,
+        });
+        let ids = Self::create_goals_batch(env, owner, nonce, goals)?;
+        ids.get(0).ok_or(SavingsGoalError::GoalNotFound)
+    }
+
+    fn new_goal(
+        env: &Env,
+        id: u32,
+        owner: &Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+    ) -> SavingsGoal {
+        SavingsGoal {
+            id,
+            owner: owner.clone(),
+            name,
+            target_amount,
+            current_amount: 0,
+            target_date,
+            locked: true,
+            unlock_date: None,
+            withdrawable_amount: 0,
+            updated_at: env.ledger().timestamp(),
+            created_at: env.ledger().timestamp(),
+            total_deposited: 0,
+            lock_on_complete: false,
+            display_currency: None,
+            rate_to_token_bps: 10_000,
+            priority: 0,
+            atomic_funding: false,
+            total_interest: 0,
+            stretch_target: 0,
+            category: None,
+            completed_at: None,
+            withdraw_only_when_complete: false,
+            token_balances: Map::new(env),
+            token_basis: Map::new(env),
+            allowed_destinations: Vec::new(env),
+            high_water_mark: 0,
+            max_interest: 0,
+            auto_roll: false,
+            roll_increment: 0,
+            roll_count: 0,
+        }
+    }
+
     /// Add funds to a savings goal
     ///
     /// # Arguments
@@ -591,6 +1017,7 @@ impl SavingsGoalContract {
         // Access control: require caller authorization
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
 
         // Input validation
         if amount <= 0 {
@@ -621,13 +1048,11 @@ impl SavingsGoalContract {
             return Err(SavingsGoalError::GoalNotFound);
         }
 
-        goal.current_amount = goal
-            .current_amount
-            .checked_add(amount)
-            .ok_or(SavingsGoalError::ArithmeticError)?;
-        let new_total = goal.current_amount;
-        let was_completed = new_total >= goal.target_amount;
-        let previously_completed = (new_total - amount) >= goal.target_amount;
+        let deposit = Self::apply_deposit(&env, &mut goal, amount)
+            .inspect_err(|_| Self::append_audit(&env, symbol_short!("add"), &caller, false))?;
+        let new_total = deposit.new_total;
+        let was_completed = deposit.was_completed;
+        let previously_completed = deposit.previously_completed;
 
         goals.set(goal_id, goal.clone());
         env.storage()
@@ -664,10 +1089,12 @@ impl SavingsGoalContract {
         if was_completed {
             env.events().publish(
                 (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                (goal_id, caller),
+                (goal_id, caller.clone()),
             );
         }
 
+        Self::publish_deposit_flags(&env, &goal, &deposit, &caller);
+
         Ok(new_total)
     }
 
@@ -679,6 +1106,7 @@ impl SavingsGoalContract {
     ) -> Result<u32, SavingsGoalError> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
         if contributions.len() as u32 > MAX_BATCH_SIZE {
             return Err(SavingsGoalError::BatchTooLarge);
         }
@@ -697,6 +1125,11 @@ impl SavingsGoalContract {
             if goal.owner != caller {
                 return Err(SavingsGoalError::NotOwnerOfAllGoals);
             }
+            if goal.atomic_funding
+                && goal.current_amount.saturating_add(item.amount) < goal.target_amount
+            {
+                return Err(SavingsGoalError::AtomicFundingIncomplete);
+            }
         }
         Self::extend_instance_ttl(&env);
         let mut goals: Map<u32, SavingsGoal> = env
@@ -712,13 +1145,10 @@ impl SavingsGoalContract {
             if goal.owner != caller {
                 return Err(SavingsGoalError::BatchValidationFailed);
             }
-            goal.current_amount = goal
-                .current_amount
-                .checked_add(item.amount)
-                .ok_or(SavingsGoalError::ArithmeticError)?;
-            let new_total = goal.current_amount;
-            let was_completed = new_total >= goal.target_amount;
-            let previously_completed = (new_total - item.amount) >= goal.target_amount;
+            let deposit = Self::apply_deposit(&env, &mut goal, item.amount)?;
+            let new_total = deposit.new_total;
+            let was_completed = deposit.was_completed;
+            let previously_completed = deposit.previously_completed;
             goals.set(item.goal_id, goal.clone());
             let funds_event = FundsAddedEvent {
                 goal_id: item.goal_id,
@@ -746,6 +1176,7 @@ impl SavingsGoalContract {
                     (item.goal_id, caller.clone()),
                 );
             }
+            Self::publish_deposit_flags(&env, &goal, &deposit, &caller);
             count += 1;
         }
         env.storage()
@@ -771,7 +1202,7 @@ impl SavingsGoalContract {
     /// # Panics
     /// - If caller is not the goal owner
     /// - If goal is not found
-    /// - If goal is locked
+    /// - If goal is locked and amount exceeds its withdrawable cap
     /// - If unlock_date is set and not yet reached
     /// - If amount is not positive
     /// - If amount exceeds current balance
@@ -784,6 +1215,7 @@ impl SavingsGoalContract {
         // Access control: require caller authorization
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
 
         // Input validation
         if amount <= 0 {
@@ -814,8 +1246,13 @@ impl SavingsGoalContract {
             return Err(SavingsGoalError::Unauthorized);
         }
 
-        // Check if goal is locked
-        if goal.locked {
+        if goal.withdraw_only_when_complete && goal.current_amount < goal.target_amount {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(SavingsGoalError::GoalNotComplete);
+        }
+
+        // A locked goal only releases up to its withdrawable cap
+        if goal.locked && amount > goal.withdrawable_amount {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
             return Err(SavingsGoalError::GoalLocked);
         }
@@ -830,7 +1267,12 @@ impl SavingsGoalContract {
         }
 
         // Check sufficient balance // NOTE: added check for target vs Amount is not needed
-        if amount > goal.current_amount {
+        // Token-backed funds only leave through the token withdrawals
+        let available = goal
+            .current_amount
+            .checked_sub(Self::token_backed_amount(&goal)?)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        if amount > available {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
             return Err(SavingsGoalError::InsufficientBalance);
         }
@@ -839,8 +1281,12 @@ impl SavingsGoalContract {
             .current_amount
             .checked_sub(amount)
             .ok_or(SavingsGoalError::ArithmeticError)?;
+        if goal.locked {
+            goal.withdrawable_amount -= amount;
+        }
         let new_amount = goal.current_amount;
 
+        goal.updated_at = env.ledger().timestamp();
         goals.set(goal_id, goal);
         env.storage()
             .instance()
@@ -855,18 +1301,33 @@ impl SavingsGoalContract {
         Ok(new_amount)
     }
 
-    /// Lock a savings goal (prevent withdrawals)
+    /// Allow part of a locked goal to be withdrawn without unlocking it
     ///
     /// # Arguments
     /// * `caller` - Address of the caller (must be the goal owner)
     /// * `goal_id` - ID of the goal
+    /// * `amount` - Withdrawable cap (0 clears it); may not exceed the current balance
     ///
     /// # Panics
-    /// - If caller is not the goal owner
+    /// - If amount is negative
     /// - If goal is not found
-    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+    /// - If caller is not the goal owner
+    /// - If amount exceeds the goal's current balance
+    pub fn set_withdrawable(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::LOCK)?;
+        Self::require_not_paused(&env, pause_functions::UNLOCK)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+
+        if amount < 0 {
+            Self::append_audit(&env, symbol_short!("set_wdr"), &caller, false);
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+
         Self::extend_instance_ttl(&env);
 
         let mut goals: Map<u32, SavingsGoal> = env
@@ -878,43 +1339,56 @@ impl SavingsGoalContract {
         let mut goal = match goals.get(goal_id) {
             Some(g) => g,
             None => {
-                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+                Self::append_audit(&env, symbol_short!("set_wdr"), &caller, false);
                 return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+            Self::append_audit(&env, symbol_short!("set_wdr"), &caller, false);
             return Err(SavingsGoalError::Unauthorized);
         }
 
-        goal.locked = true;
+        if amount > goal.current_amount {
+            Self::append_audit(&env, symbol_short!("set_wdr"), &caller, false);
+            return Err(SavingsGoalError::InsufficientBalance);
+        }
+
+        goal.withdrawable_amount = amount;
+        goal.updated_at = env.ledger().timestamp();
         goals.set(goal_id, goal);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
 
-        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        Self::append_audit(&env, symbol_short!("set_wdr"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalLocked),
-            (goal_id, caller),
+            (symbol_short!("savings"), SavingsEvent::WithdrawableSet),
+            (goal_id, caller, amount),
         );
 
         Ok(true)
     }
 
-    /// Unlock a savings goal (allow withdrawals)
+    /// Choose whether a goal locks itself once deposits reach its target
     ///
     /// # Arguments
     /// * `caller` - Address of the caller (must be the goal owner)
     /// * `goal_id` - ID of the goal
+    /// * `enabled` - True to lock on completion (default false)
     ///
     /// # Panics
     /// - If caller is not the goal owner
     /// - If goal is not found
-    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+    pub fn set_lock_on_complete(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        enabled: bool,
+    ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::UNLOCK)?;
+        Self::require_not_paused(&env, pause_functions::LOCK)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
         Self::extend_instance_ttl(&env);
 
         let mut goals: Map<u32, SavingsGoal> = env
@@ -926,646 +1400,2721 @@ impl SavingsGoalContract {
         let mut goal = match goals.get(goal_id) {
             Some(g) => g,
             None => {
-                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+                Self::append_audit(&env, symbol_short!("lock_cmp"), &caller, false);
                 return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            Self::append_audit(&env, symbol_short!("lock_cmp"), &caller, false);
             return Err(SavingsGoalError::Unauthorized);
         }
 
-        goal.locked = false;
+        goal.lock_on_complete = enabled;
+        goal.updated_at = env.ledger().timestamp();
         goals.set(goal_id, goal);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
 
-        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
-            (goal_id, caller),
-        );
-
+        Self::append_audit(&env, symbol_short!("lock_cmp"), &caller, true);
         Ok(true)
     }
 
-    /// Get a savings goal by ID
+    /// Only allow withdrawals once a goal has reached its target (commitment savings).
+    /// Independent of the lock flag: both checks apply.
     ///
     /// # Arguments
+    /// * `caller` - Address of the caller (must be the goal owner)
     /// * `goal_id` - ID of the goal
+    /// * `enabled` - True to reject withdrawals while the goal is under target
     ///
-    /// # Returns
-    /// SavingsGoal struct or None if not found
-    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn set_withdraw_only_when_complete(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        enabled: bool,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
 
-        goals.get(goal_id)
-    }
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
 
-    /// Get all savings goals for a specific owner
+        goal.withdraw_only_when_complete = enabled;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("wd_rule"), &caller, true);
+        Ok(true)
+    }
+
+    /// Require deposits to a goal to complete it in one call (all-or-nothing funding)
     ///
     /// # Arguments
-    /// * `owner` - Address of the goal owner
-    ///
-    /// # Returns
-    /// Vec of all SavingsGoal structs belonging to the owner
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    /// * `enabled` - True to reject deposits that leave the goal short of its target
     ///
-    /// # Note
-    /// This function can be expensive with large datasets. Consider using get_goals_paginated
-    /// for better performance when dealing with many goals.
-    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn set_atomic_funding(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        enabled: bool,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
 
-        let mut result = Vec::new(&env);
-        for (_, goal) in goals.iter() {
-            if goal.owner == owner {
-                result.push_back(goal);
-            }
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
         }
-        result
+
+        goal.atomic_funding = enabled;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("atomic"), &caller, true);
+        Ok(true)
     }
 
-    /// Get savings goals for a specific owner with pagination
+    /// Set a stretch target above the goal's main target. Reaching it emits
+    /// `StretchReached`; completion of the main target is unaffected.
     ///
     /// # Arguments
-    /// * `owner` - Address of the goal owner
-    /// * `cursor` - Optional cursor (goal_id) to start from. None for first page
-    /// * `limit` - Maximum number of goals to return (max 100, default 20)
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    /// * `stretch_target` - Must exceed `target_amount`; 0 clears the stretch target
     ///
-    /// # Returns
-    /// PaginatedGoalsResponse containing the goals, pagination info, and next cursor
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    /// - If stretch_target is non-zero and not above the main target
+    pub fn set_stretch_target(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        stretch_target: i128,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if stretch_target != 0 && stretch_target <= goal.target_amount {
+            Self::append_audit(&env, symbol_short!("stretch"), &caller, false);
+            return Err(SavingsGoalError::InvalidStretchTarget);
+        }
+
+        goal.stretch_target = stretch_target;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("stretch"), &caller, true);
+        Ok(true)
+    }
+
+    /// Turn a goal into a recurring challenge: each time a deposit reaches the target,
+    /// the target is raised by `roll_increment` and the goal counts as incomplete again.
     ///
-    /// # Performance
-    /// This function is optimized for large datasets by using indexed iteration
-    /// and early termination when the limit is reached.
-    pub fn get_goals_paginated(
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    /// * `enabled` - True to roll the target forward on completion
+    /// * `roll_increment` - Amount added to the target per roll; must be positive when enabled
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    /// - If enabled with a non-positive roll_increment
+    pub fn set_auto_roll(
         env: Env,
-        owner: Address,
-        cursor: Option<u32>,
-        limit: Option<u32>,
-    ) -> PaginatedGoalsResponse {
-        let effective_limit = limit
-            .unwrap_or(DEFAULT_PAGE_LIMIT)
-            .min(MAX_PAGE_LIMIT)
-            .max(1);
+        caller: Address,
+        goal_id: u32,
+        enabled: bool,
+        roll_increment: i128,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
 
-        let goals: Map<u32, SavingsGoal> = env
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
 
-        let mut result = Vec::new(&env);
-        let mut count = 0u32;
-        let mut has_more = false;
-        let mut next_cursor: Option<u32> = None;
-        let mut found_cursor = cursor.is_none(); // Start from beginning if no cursor
-        let mut last_returned_goal_id: Option<u32> = None;
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if enabled && roll_increment <= 0 {
+            Self::append_audit(&env, symbol_short!("auto_roll"), &caller, false);
+            return Err(SavingsGoalError::InvalidRollIncrement);
+        }
 
-        for (goal_id, goal) in goals.iter() {
-            // Skip until we find the cursor position
-            if !found_cursor {
-                if let Some(cursor_id) = cursor {
-                    if goal_id == cursor_id {
-                        found_cursor = true;
-                    }
-                }
-                continue;
-            }
+        goal.auto_roll = enabled;
+        goal.roll_increment = if enabled { roll_increment } else { 0 };
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
 
-            if goal.owner == owner {
-                if count < effective_limit {
-                    result.push_back(goal);
-                    count += 1;
-                    last_returned_goal_id = Some(goal_id);
-                } else {
-                    has_more = true;
-                    next_cursor = last_returned_goal_id;
-                    break;
-                }
-            }
+        Self::append_audit(&env, symbol_short!("auto_roll"), &caller, true);
+        Ok(true)
+    }
+
+    /// Times a goal's target has been rolled forward (0 for unknown goals)
+    pub fn get_roll_count(env: Env, goal_id: u32) -> u32 {
+        Self::get_goal(env, goal_id)
+            .map(|goal| goal.roll_count)
+            .unwrap_or(0)
+    }
+
+    /// Raise a reached auto-roll target past `current_amount` in whole increments and
+    /// clear its completion. Returns whether the target moved.
+    fn roll_forward(goal: &mut SavingsGoal) -> bool {
+        if !goal.auto_roll || goal.roll_increment <= 0 || goal.current_amount < goal.target_amount {
+            return false;
         }
+        let rolls = (goal.current_amount - goal.target_amount) / goal.roll_increment + 1;
+        goal.target_amount = goal
+            .target_amount
+            .saturating_add(rolls.saturating_mul(goal.roll_increment));
+        goal.roll_count = goal
+            .roll_count
+            .saturating_add(u32::try_from(rolls).unwrap_or(u32::MAX));
+        goal.completed_at = None;
+        true
+    }
 
-        PaginatedGoalsResponse {
-            goals: result,
-            has_more,
-            next_cursor,
+    /// Check if a goal has a stretch target and has reached it
+    pub fn is_stretch_reached(env: Env, goal_id: u32) -> bool {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => goal.stretch_target > 0 && goal.current_amount >= goal.stretch_target,
+            None => false,
         }
     }
 
-    /// Check if a goal is completed
-    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
-        let storage = env.storage().instance();
-        let goals: Map<u32, SavingsGoal> = storage
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or(Map::new(&env));
-        if let Some(goal) = goals.get(goal_id) {
-            goal.current_amount >= goal.target_amount
-        } else {
-            false
+    /// Stamp `completed_at` the first time a goal reaches its target
+    fn record_completion(goal: &mut SavingsGoal, now: u64) {
+        if goal.completed_at.is_none() && goal.current_amount >= goal.target_amount {
+            goal.completed_at = Some(now);
         }
     }
 
-    /// Get current nonce for an address (for import_snapshot replay protection).
-    pub fn get_nonce(env: Env, address: Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address))
-            .unwrap_or(0)
+    /// Raise `high_water_mark` to the current balance if it is a new peak
+    fn record_high_water(goal: &mut SavingsGoal) {
+        if goal.current_amount > goal.high_water_mark {
+            goal.high_water_mark = goal.current_amount;
+        }
     }
 
-    /// Export all goals as snapshot for backup/migration.
-    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
-        caller.require_auth();
-        let goals: Map<u32, SavingsGoal> = env
+    /// Append the current ledger time to the goal's contribution history
+    fn record_contribution(env: &Env, goal_id: u32) {
+        let mut history: Map<u32, Vec<u64>> = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let next_id = env
-            .storage()
+            .get(&symbol_short!("CONTRIBS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut timestamps = history.get(goal_id).unwrap_or_else(|| Vec::new(env));
+        if timestamps.len() >= MAX_CONTRIBUTION_HISTORY {
+            timestamps.pop_front();
+        }
+        timestamps.push_back(env.ledger().timestamp());
+        history.set(goal_id, timestamps);
+        env.storage()
             .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-        let mut list = Vec::new(&env);
-        for i in 1..=next_id {
-            if let Some(g) = goals.get(i) {
-                list.push_back(g);
-            }
+            .set(&symbol_short!("CONTRIBS"), &history);
+    }
+
+    /// Credit `amount` to `goal` and record it in the goal's history, then apply
+    /// `lock_on_complete` and `auto_roll`. Goals with `atomic_funding` only accept a
+    /// deposit that reaches the target; a rejected deposit leaves `goal` untouched.
+    fn apply_deposit(
+        env: &Env,
+        goal: &mut SavingsGoal,
+        amount: i128,
+    ) -> Result<Deposit, SavingsGoalError> {
+        let new_total = goal
+            .current_amount
+            .checked_add(amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        if goal.atomic_funding && new_total < goal.target_amount {
+            return Err(SavingsGoalError::AtomicFundingIncomplete);
         }
-        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
-        GoalsExportSnapshot {
-            version: SNAPSHOT_VERSION,
-            checksum,
-            next_id,
-            goals: list,
+        let now = env.ledger().timestamp();
+        let previously_completed = goal.current_amount >= goal.target_amount;
+        goal.current_amount = new_total;
+        goal.total_deposited = goal.total_deposited.saturating_add(amount);
+        Self::record_high_water(goal);
+        Self::record_completion(goal, now);
+        Self::record_contribution(env, goal.id);
+        goal.updated_at = now;
+        let was_completed = new_total >= goal.target_amount;
+        let auto_locked =
+            was_completed && !previously_completed && goal.lock_on_complete && !goal.locked;
+        if auto_locked {
+            goal.locked = true;
         }
+        let stretch_crossed = Self::stretch_crossed(goal, new_total - amount);
+        let rolled = was_completed && Self::roll_forward(goal);
+        Ok(Deposit {
+            new_total,
+            previously_completed,
+            was_completed,
+            auto_locked,
+            rolled,
+            stretch_crossed,
+        })
     }
 
-    /// Import snapshot (full restore). Validates version and checksum. Requires nonce for replay protection.
-    pub fn import_snapshot(
-        env: Env,
-        caller: Address,
-        nonce: u64,
-        snapshot: GoalsExportSnapshot,
-    ) -> Result<bool, SavingsGoalError> {
-        caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce)?;
-
-        if snapshot.version != SNAPSHOT_VERSION {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(SavingsGoalError::UnsupportedSnapshotVersion);
+    /// Publish the roll, auto-lock and stretch events a deposit triggered
+    fn publish_deposit_flags(env: &Env, goal: &SavingsGoal, deposit: &Deposit, caller: &Address) {
+        if deposit.rolled {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalRolled),
+                (goal.id, caller.clone(), goal.target_amount),
+            );
         }
-        let expected =
-            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
-        if snapshot.checksum != expected {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(SavingsGoalError::SnapshotChecksumMismatch);
+        if deposit.auto_locked {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalLocked),
+                (goal.id, caller.clone()),
+            );
         }
+        if deposit.stretch_crossed {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::StretchReached),
+                (goal.id, caller.clone()),
+            );
+        }
+    }
+
+    /// True when a deposit moved `goal` from `previous_amount` to at or past its stretch target
+    fn stretch_crossed(goal: &SavingsGoal, previous_amount: i128) -> bool {
+        goal.stretch_target > 0
+            && previous_amount < goal.stretch_target
+            && goal.current_amount >= goal.stretch_target
+    }
+
+    /// Lock a savings goal (prevent withdrawals)
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::LOCK)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = match goals.get(goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        goal.locked = true;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalLocked),
+            (goal_id, caller),
+        );
+
+        Ok(true)
+    }
+
+    /// Unlock a savings goal (allow withdrawals)
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UNLOCK)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = match goals.get(goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        goal.locked = false;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
+            (goal_id, caller),
+        );
+
+        Ok(true)
+    }
+
+    /// Close all of the caller's goals, sweeping the tokens they hold to `destination`.
+    /// Only token-backed balances (`get_goal_token_balances`) are transferred, each in
+    /// its own token; amounts that were only ever recorded with `add_to_goal` are
+    /// dropped with the goal.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `nonce` - Caller's transaction nonce (must equal get_nonce(caller))
+    /// * `destination` - Address receiving the swept funds
+    ///
+    /// # Returns
+    /// Total swept, normalized with `get_token_rate`. Goals that are locked, time-locked
+    /// or held by `withdraw_only_when_complete` are skipped and reported in the `closed`
+    /// event. Closed goals lose their schedules and contribution history with them.
+    pub fn close_account(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        destination: Address,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let current_time = env.ledger().timestamp();
+        let mut total_swept = 0i128;
+        let mut swept_by_token: Map<Address, i128> = Map::new(&env);
+        let mut closed: Vec<u32> = Vec::new(&env);
+        let mut skipped: Vec<u32> = Vec::new(&env);
+
+        for (goal_id, goal) in goals.iter() {
+            if goal.owner != caller {
+                continue;
+            }
+            let time_locked = goal
+                .unlock_date
+                .is_some_and(|unlock_date| current_time < unlock_date);
+            let held = goal.withdraw_only_when_complete && goal.current_amount < goal.target_amount;
+            if goal.locked || time_locked || held {
+                skipped.push_back(goal_id);
+                continue;
+            }
+            if !Self::destination_allowed(&goal, &destination) {
+                Self::append_audit(&env, symbol_short!("close"), &caller, false);
+                return Err(SavingsGoalError::DestinationNotAllowed);
+            }
+            for (token, amount) in goal.token_balances.iter() {
+                let swept = swept_by_token
+                    .get(token.clone())
+                    .unwrap_or(0)
+                    .checked_add(amount)
+                    .ok_or(SavingsGoalError::ArithmeticError)?;
+                total_swept = total_swept
+                    .checked_add(Self::normalize_token_amount(&env, &token, amount)?)
+                    .ok_or(SavingsGoalError::ArithmeticError)?;
+                swept_by_token.set(token, swept);
+            }
+            closed.push_back(goal_id);
+        }
+
+        for goal_id in closed.iter() {
+            goals.remove(goal_id);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        Self::purge_goal_keys(&env, &closed);
+
+        for (token, amount) in swept_by_token.iter() {
+            if amount > 0 {
+                TokenClient::new(&env, &token).transfer(
+                    &env.current_contract_address(),
+                    &destination,
+                    &amount,
+                );
+            }
+        }
+
+        Self::increment_nonce(&env, &caller);
+        Self::append_audit(&env, symbol_short!("close"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("closed")),
+            (caller, total_swept, closed, skipped),
+        );
+
+        Ok(total_swept)
+    }
+
+    /// Drop the contribution schedules, savings schedules and contribution history
+    /// kept for `goal_ids`
+    fn purge_goal_keys(env: &Env, goal_ids: &Vec<u32>) {
+        let mut contributions: Map<u32, ContributionSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIB"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut history: Map<u32, Vec<u64>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIBS"))
+            .unwrap_or_else(|| Map::new(env));
+        for goal_id in goal_ids.iter() {
+            contributions.remove(goal_id);
+            history.remove(goal_id);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONTRIB"), &contributions);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONTRIBS"), &history);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut orphaned: Vec<u32> = Vec::new(env);
+        for (schedule_id, schedule) in schedules.iter() {
+            if goal_ids.contains(schedule.goal_id) {
+                orphaned.push_back(schedule_id);
+            }
+        }
+        for schedule_id in orphaned.iter() {
+            schedules.remove(schedule_id);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+    }
+
+    /// Fold `source_goal` into `dest_goal`: the source's current and target amounts are
+    /// added to the destination and the source goal is removed, along with any
+    /// contribution schedule attached to it.
+    ///
+    /// # Returns
+    /// The destination's new current amount
+    ///
+    /// # Panics
+    /// - If the two ids are the same
+    /// - If caller does not own both goals
+    /// - If either goal is locked or time-locked
+    pub fn merge_goals(
+        env: Env,
+        caller: Address,
+        source_goal: u32,
+        dest_goal: u32,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+
+        if source_goal == dest_goal {
+            Self::append_audit(&env, symbol_short!("merge"), &caller, false);
+            return Err(SavingsGoalError::CannotMergeIntoSelf);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let (source, mut dest) = match (goals.get(source_goal), goals.get(dest_goal)) {
+            (Some(s), Some(d)) if s.owner == caller && d.owner == caller => (s, d),
+            _ => {
+                Self::append_audit(&env, symbol_short!("merge"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+
+        let now = env.ledger().timestamp();
+        for goal in [&source, &dest] {
+            if goal.locked {
+                Self::append_audit(&env, symbol_short!("merge"), &caller, false);
+                return Err(SavingsGoalError::GoalLocked);
+            }
+            if let Some(unlock_date) = goal.unlock_date {
+                if now < unlock_date {
+                    Self::append_audit(&env, symbol_short!("merge"), &caller, false);
+                    return Err(SavingsGoalError::TimeLocked);
+                }
+            }
+        }
+
+        let previously_completed = dest.current_amount >= dest.target_amount;
+        dest.current_amount = dest
+            .current_amount
+            .checked_add(source.current_amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        dest.target_amount = dest
+            .target_amount
+            .checked_add(source.target_amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        dest.total_deposited = dest.total_deposited.saturating_add(source.total_deposited);
+        for (token, amount) in source.token_balances.iter() {
+            let balance = dest
+                .token_balances
+                .get(token.clone())
+                .unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(SavingsGoalError::ArithmeticError)?;
+            dest.token_balances.set(token, balance);
+        }
+        for (token, basis) in source.token_basis.iter() {
+            let total = dest
+                .token_basis
+                .get(token.clone())
+                .unwrap_or(0)
+                .checked_add(basis)
+                .ok_or(SavingsGoalError::ArithmeticError)?;
+            dest.token_basis.set(token, total);
+        }
+        Self::record_high_water(&mut dest);
+        Self::record_completion(&mut dest, now);
+        dest.updated_at = now;
+        let is_completed = dest.current_amount >= dest.target_amount;
+        let new_total = dest.current_amount;
+
+        goals.remove(source_goal);
+        goals.set(dest_goal, dest.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        let mut contributions: Map<u32, ContributionSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIB"))
+            .unwrap_or_else(|| Map::new(&env));
+        if contributions.contains_key(source_goal) {
+            contributions.remove(source_goal);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CONTRIB"), &contributions);
+        }
+
+        Self::append_audit(&env, symbol_short!("merge"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalsMerged),
+            (source_goal, dest_goal, source.current_amount),
+        );
+
+        if is_completed && !previously_completed {
+            let completed_event = GoalCompletedEvent {
+                goal_id: dest_goal,
+                name: dest.name,
+                final_amount: new_total,
+                timestamp: now,
+            };
+            env.events().publish((GOAL_COMPLETED,), completed_event);
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                (dest_goal, caller),
+            );
+        }
+
+        Ok(new_total)
+    }
+
+    /// Set a goal's priority for `allocate_savings_by_priority` (higher is funded first)
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn set_goal_priority(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        priority: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        goal.priority = priority;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Ok(true)
+    }
+
+    /// Spread a savings amount over the caller's unfinished goals, highest priority first
+    /// (ties go to the older goal). Each goal receives at most its remaining-to-target, and
+    /// goals with `atomic_funding` are skipped unless their share completes them. Any
+    /// residue goes to the highest-priority goal that accepts it.
+    ///
+    /// # Returns
+    /// The amount credited to each goal, in allocation order
+    ///
+    /// # Panics
+    /// - If amount is not positive
+    /// - If the nonce is invalid
+    /// - If the caller has no goals
+    /// - If the residue would leave an `atomic_funding` goal short of its target
+    pub fn allocate_savings_by_priority(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        amount: i128,
+    ) -> Result<Vec<GoalAllocation>, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("alloc"), &caller, false);
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        // Caller's goals ordered by descending priority, then ascending id
+        let mut ordered: Vec<SavingsGoal> = Vec::new(&env);
+        for (_, goal) in goals.iter() {
+            if goal.owner != caller {
+                continue;
+            }
+            let mut pos = ordered.len();
+            for (i, existing) in ordered.iter().enumerate() {
+                if goal.priority > existing.priority {
+                    pos = i as u32;
+                    break;
+                }
+            }
+            ordered.insert(pos, goal);
+        }
+        if ordered.is_empty() {
+            Self::append_audit(&env, symbol_short!("alloc"), &caller, false);
+            return Err(SavingsGoalError::GoalNotFound);
+        }
+
+        let mut credits: Map<u32, i128> = Map::new(&env);
+        let mut allocations: Vec<GoalAllocation> = Vec::new(&env);
+        let mut remaining = amount;
+        for goal in ordered.iter() {
+            if remaining == 0 {
+                break;
+            }
+            let room = goal.target_amount - goal.current_amount;
+            if room <= 0 {
+                continue;
+            }
+            if goal.atomic_funding && room > remaining {
+                continue;
+            }
+            let credit = room.min(remaining);
+            remaining -= credit;
+            credits.set(goal.id, credit);
+            allocations.push_back(GoalAllocation {
+                goal_id: goal.id,
+                amount: credit,
+            });
+        }
+        if remaining > 0 {
+            let top = match ordered.iter().find(|goal| {
+                !goal.atomic_funding
+                    || goal
+                        .current_amount
+                        .saturating_add(credits.get(goal.id).unwrap_or(0))
+                        .saturating_add(remaining)
+                        >= goal.target_amount
+            }) {
+                Some(goal) => goal.id,
+                None => {
+                    Self::append_audit(&env, symbol_short!("alloc"), &caller, false);
+                    return Err(SavingsGoalError::AtomicFundingIncomplete);
+                }
+            };
+            let credit = credits.get(top).unwrap_or(0) + remaining;
+            credits.set(top, credit);
+            let mut found = false;
+            for i in 0..allocations.len() {
+                let mut entry = allocations.get(i).unwrap();
+                if entry.goal_id == top {
+                    entry.amount = credit;
+                    allocations.set(i, entry);
+                    found = true;
+                }
+            }
+            if !found {
+                allocations.push_back(GoalAllocation {
+                    goal_id: top,
+                    amount: remaining,
+                });
+            }
+        }
+
+        for (goal_id, credit) in credits.iter() {
+            let mut goal = goals.get(goal_id).unwrap();
+            let deposit = Self::apply_deposit(&env, &mut goal, credit)?;
+            goals.set(goal_id, goal.clone());
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                (goal_id, caller.clone(), credit),
+            );
+            Self::publish_deposit_flags(&env, &goal, &deposit, &caller);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::increment_nonce(&env, &caller);
+        Self::append_audit(&env, symbol_short!("alloc"), &caller, true);
+
+        Ok(allocations)
+    }
+
+    /// Get a savings goal by ID
+    ///
+    /// # Arguments
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Returns
+    /// SavingsGoal struct or None if not found
+    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        goals.get(goal_id)
+    }
+
+    /// ID the next `create_goal` will assign. Read-only; any goal created in between
+    /// (by this or another owner) takes the id first, so treat it as a hint.
+    pub fn peek_next_goal_id(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+            + 1
+    }
+
+    /// Get all savings goals for a specific owner
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the goal owner
+    ///
+    /// # Returns
+    /// Vec of all SavingsGoal structs belonging to the owner
+    ///
+    /// # Note
+    /// This function can be expensive with large datasets. Consider using get_goals_paginated
+    /// for better performance when dealing with many goals.
+    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (_, goal) in goals.iter() {
+            if goal.owner == owner {
+                result.push_back(goal);
+            }
+        }
+        result
+    }
+
+    /// Get savings goals for a specific owner with pagination
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the goal owner
+    /// * `cursor` - Optional cursor (goal_id) to start from. None for first page
+    /// * `limit` - Maximum number of goals to return (max 100, default 20)
+    ///
+    /// # Returns
+    /// PaginatedGoalsResponse containing the goals, pagination info, and next cursor
+    ///
+    /// # Performance
+    /// This function is optimized for large datasets by using indexed iteration
+    /// and early termination when the limit is reached.
+    pub fn get_goals_paginated(
+        env: Env,
+        owner: Address,
+        cursor: Option<u32>,
+        limit: Option<u32>,
+    ) -> PaginatedGoalsResponse {
+        let effective_limit = limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .min(MAX_PAGE_LIMIT)
+            .max(1);
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        let mut count = 0u32;
+        let mut has_more = false;
+        let mut next_cursor: Option<u32> = None;
+        let mut found_cursor = cursor.is_none(); // Start from beginning if no cursor
+        let mut last_returned_goal_id: Option<u32> = None;
+
+        for (goal_id, goal) in goals.iter() {
+            // Skip until we find the cursor position
+            if !found_cursor {
+                if let Some(cursor_id) = cursor {
+                    if goal_id == cursor_id {
+                        found_cursor = true;
+                    }
+                }
+                continue;
+            }
+
+            if goal.owner == owner {
+                if count < effective_limit {
+                    result.push_back(goal);
+                    count += 1;
+                    last_returned_goal_id = Some(goal_id);
+                } else {
+                    has_more = true;
+                    next_cursor = last_returned_goal_id;
+                    break;
+                }
+            }
+        }
+
+        PaginatedGoalsResponse {
+            goals: result,
+            has_more,
+            next_cursor,
+        }
+    }
+
+    /// Get goals of any owner that have not changed for at least `dormancy_secs` (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the pause admin)
+    /// * `dormancy_secs` - Minimum idle time for a goal to count as dormant
+    /// * `now_ts` - Reference timestamp; goals with `updated_at <= now_ts - dormancy_secs` match
+    /// * `cursor` - Optional cursor (goal_id) to start after. None for first page
+    /// * `limit` - Maximum number of goals to return (max 100, default 20)
+    ///
+    /// # Returns
+    /// PaginatedGoalsResponse containing the dormant goals
+    ///
+    /// # Panics
+    /// - If caller is not the pause admin
+    pub fn get_dormant_goals(
+        env: Env,
+        caller: Address,
+        dormancy_secs: u64,
+        now_ts: u64,
+        cursor: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<PaginatedGoalsResponse, SavingsGoalError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if admin != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let effective_limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        let cutoff = now_ts.saturating_sub(dormancy_secs);
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        let mut has_more = false;
+        let mut next_cursor: Option<u32> = None;
+        let mut last_returned_goal_id: Option<u32> = None;
+
+        for (goal_id, goal) in goals.iter() {
+            if let Some(cursor_id) = cursor {
+                if goal_id <= cursor_id {
+                    continue;
+                }
+            }
+
+            if goal.updated_at <= cutoff {
+                if result.len() < effective_limit {
+                    result.push_back(goal);
+                    last_returned_goal_id = Some(goal_id);
+                } else {
+                    has_more = true;
+                    next_cursor = last_returned_goal_id;
+                    break;
+                }
+            }
+        }
+
+        Ok(PaginatedGoalsResponse {
+            goals: result,
+            has_more,
+            next_cursor,
+        })
+    }
+
+    /// List distinct goal owners, in order of their first goal (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the pause admin)
+    /// * `offset` - Number of distinct owners to skip
+    /// * `limit` - Maximum number of owners to return (max 100, default 20)
+    ///
+    /// # Panics
+    /// - If caller is not the pause admin
+    pub fn get_owners(
+        env: Env,
+        caller: Address,
+        offset: u32,
+        limit: Option<u32>,
+    ) -> Result<Vec<Address>, SavingsGoalError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if admin != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let effective_limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut seen: Map<Address, bool> = Map::new(&env);
+        let mut skipped = 0u32;
+        let mut result = Vec::new(&env);
+        for (_, goal) in goals.iter() {
+            if seen.contains_key(goal.owner.clone()) {
+                continue;
+            }
+            seen.set(goal.owner.clone(), true);
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            result.push_back(goal.owner);
+            if result.len() >= effective_limit {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Outcome statistics over every goal in the contract, in one scan (pause admin
+    /// only). Goals share a single instance-storage entry, so the scan is bounded by
+    /// what that entry can hold. Amount sums saturate instead of overflowing.
+    ///
+    /// # Panics
+    /// - If caller is not the pause admin
+    pub fn get_completion_stats(
+        env: Env,
+        caller: Address,
+    ) -> Result<CompletionStats, SavingsGoalError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if admin != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut stats = CompletionStats {
+            total_goals: 0,
+            completed_goals: 0,
+            total_target: 0,
+            total_saved: 0,
+            completion_rate_bps: 0,
+        };
+        for (_, goal) in goals.iter() {
+            stats.total_goals += 1;
+            if goal.completed_at.is_some() || goal.current_amount >= goal.target_amount {
+                stats.completed_goals += 1;
+            }
+            stats.total_target = stats.total_target.saturating_add(goal.target_amount);
+            stats.total_saved = stats.total_saved.saturating_add(goal.current_amount);
+        }
+        if stats.total_goals > 0 {
+            stats.completion_rate_bps =
+                (stats.completed_goals as u64 * 10_000 / stats.total_goals as u64) as u32;
+        }
+        Ok(stats)
+    }
+
+    /// Goals of `owner` that first reached their target between `start_ts` and `end_ts`
+    /// (both inclusive)
+    pub fn get_goals_completed_between(
+        env: Env,
+        owner: Address,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Vec<SavingsGoal> {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (_, goal) in goals.iter() {
+            if goal.owner != owner {
+                continue;
+            }
+            if let Some(completed_at) = goal.completed_at {
+                if completed_at >= start_ts && completed_at <= end_ts {
+                    result.push_back(goal);
+                }
+            }
+        }
+        result
+    }
+
+    /// Check if a goal is completed
+    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
+        let storage = env.storage().instance();
+        let goals: Map<u32, SavingsGoal> = storage
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or(Map::new(&env));
+        if let Some(goal) = goals.get(goal_id) {
+            goal.current_amount >= goal.target_amount
+        } else {
+            false
+        }
+    }
+
+    /// UI hint for what the owner should do next with a goal, as of `now_ts`:
+    /// - `FUND`: under target and due within 30 days (or overdue)
+    /// - `ON_TRACK`: under target with more than 30 days left
+    /// - `LOCKED`: target reached but the goal is locked or time-locked
+    /// - `WITHDRAW`: target reached and funds can be withdrawn
+    /// - `DONE`: target reached and everything withdrawn
+    /// - `NONE`: no such goal
+    pub fn get_next_action(env: Env, goal_id: u32, now_ts: u64) -> Symbol {
+        let goal = match Self::get_goal(env, goal_id) {
+            Some(goal) => goal,
+            None => return symbol_short!("NONE"),
+        };
+
+        let reached = goal.completed_at.is_some() || goal.current_amount >= goal.target_amount;
+        if !reached {
+            if goal.target_date <= now_ts.saturating_add(FUND_HINT_WINDOW_SECS) {
+                return symbol_short!("FUND");
+            }
+            return symbol_short!("ON_TRACK");
+        }
+        if goal.current_amount == 0 {
+            return symbol_short!("DONE");
+        }
+        let time_locked = matches!(goal.unlock_date, Some(unlock_date) if now_ts < unlock_date);
+        if goal.locked || time_locked {
+            return symbol_short!("LOCKED");
+        }
+        symbol_short!("WITHDRAW")
+    }
+
+    /// Projected timestamp at which the goal reaches its target, extrapolating the
+    /// average deposit rate (`total_deposited` over the time since `created_at`).
+    ///
+    /// Returns 0 if the goal is already complete, and `u64::MAX` if the goal doesn't
+    /// exist or the rate is zero (no deposits yet, or no time has elapsed).
+    pub fn estimate_completion(env: Env, goal_id: u32, now_ts: u64) -> u64 {
+        let goal = match Self::get_goal(env, goal_id) {
+            Some(goal) => goal,
+            None => return u64::MAX,
+        };
+        if goal.current_amount >= goal.target_amount {
+            return 0;
+        }
+
+        let elapsed = now_ts.saturating_sub(goal.created_at);
+        if elapsed == 0 || goal.total_deposited <= 0 {
+            return u64::MAX;
+        }
+
+        // remaining / (total_deposited / elapsed), rounded up to whole seconds
+        let remaining = goal.target_amount - goal.current_amount;
+        let secs_needed = remaining.checked_mul(elapsed as i128).map(|scaled| {
+            scaled / goal.total_deposited + i128::from(scaled % goal.total_deposited != 0)
+        });
+        match secs_needed {
+            Some(secs) if secs <= (u64::MAX - now_ts) as i128 => now_ts + secs as u64,
+            _ => u64::MAX,
+        }
+    }
+
+    /// Timestamps of the goal's most recent deposits (up to `MAX_CONTRIBUTION_HISTORY`),
+    /// oldest first
+    pub fn get_contribution_history(env: Env, goal_id: u32) -> Vec<u64> {
+        let history: Map<u32, Vec<u64>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIBS"))
+            .unwrap_or_else(|| Map::new(&env));
+        history.get(goal_id).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// How regularly the owner has contributed to the goal, from 0 to 100.
+    ///
+    /// Takes the gaps between consecutive contributions in the history, plus the
+    /// gap from the last contribution to `now_ts` when that is longer than the
+    /// average gap (so a stalled goal loses points). With `mean` the average gap and
+    /// `dev` the mean absolute deviation of the gaps from it:
+    ///
+    /// `score = 100 * mean / (mean + dev)`
+    ///
+    /// Evenly spaced deposits score 100; the more bursty, the lower. Goals with
+    /// fewer than three contributions, or whose deposits all share one timestamp,
+    /// score 0.
+    pub fn get_consistency(env: Env, goal_id: u32, now_ts: u64) -> u32 {
+        let history = Self::get_contribution_history(env.clone(), goal_id);
+        if history.len() < 3 {
+            return 0;
+        }
+
+        let mut gaps = Vec::new(&env);
+        let mut previous = history.get(0).unwrap_or(0);
+        for ts in history.iter().skip(1) {
+            gaps.push_back(ts.saturating_sub(previous) as i128);
+            previous = ts;
+        }
+        let sum: i128 = gaps.iter().sum();
+        let since_last = now_ts.saturating_sub(previous) as i128;
+        if since_last * (gaps.len() as i128) > sum {
+            gaps.push_back(since_last);
+        }
+
+        let count = gaps.len() as i128;
+        let mean = gaps.iter().sum::<i128>() / count;
+        if mean == 0 {
+            return 0;
+        }
+        let dev = gaps.iter().map(|gap| (gap - mean).abs()).sum::<i128>() / count;
+        (100 * mean / (mean + dev)) as u32
+    }
+
+    /// Why a withdrawal from the goal would be blocked as of `now_ts`, checked in the
+    /// order `withdraw_from_goal` applies them:
+    /// - `FROZEN`: the owner is frozen by the admin, or withdrawals are paused
+    /// - `MANUAL_LOCK`: the goal is locked by its owner
+    /// - `TIME_LOCK`: the goal's `unlock_date` has not passed
+    /// - `INCOMPLETE`: withdraw-only-when-complete is set and the target isn't reached
+    /// - `UNLOCKED`: nothing blocks a withdrawal
+    /// - `NONE`: no such goal
+    pub fn get_lock_status(env: Env, goal_id: u32, now_ts: u64) -> Symbol {
+        let goal = match Self::get_goal(env.clone(), goal_id) {
+            Some(goal) => goal,
+            None => return symbol_short!("NONE"),
+        };
+
+        if Self::is_owner_frozen(&env, &goal.owner)
+            || Self::require_not_paused(&env, pause_functions::WITHDRAW).is_err()
+        {
+            return symbol_short!("FROZEN");
+        }
+        if goal.locked {
+            return Symbol::new(&env, "MANUAL_LOCK");
+        }
+        if matches!(goal.unlock_date, Some(unlock_date) if now_ts < unlock_date) {
+            return symbol_short!("TIME_LOCK");
+        }
+        if goal.withdraw_only_when_complete && goal.current_amount < goal.target_amount {
+            return Symbol::new(&env, "INCOMPLETE");
+        }
+        symbol_short!("UNLOCKED")
+    }
+
+    /// Amount still needed to reach the target (0 once reached, or if the goal doesn't exist)
+    pub fn get_remaining_to_target(env: Env, goal_id: u32) -> i128 {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => (goal.target_amount - goal.current_amount).max(0),
+            None => 0,
+        }
+    }
+
+    /// Amount saved beyond the target (0 unless over-funded, or if the goal doesn't exist)
+    pub fn get_surplus(env: Env, goal_id: u32) -> i128 {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => (goal.current_amount - goal.target_amount).max(0),
+            None => 0,
+        }
+    }
+
+    /// Credit accrued interest to a goal. The amount is added to the goal balance and to
+    /// its `total_interest` ledger. Only the pause admin may post interest. When the goal
+    /// has a `max_interest` cap, only the remaining budget is credited.
+    ///
+    /// # Returns
+    /// The goal's updated `total_interest`
+    pub fn credit_interest(
+        env: Env,
+        admin: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalError> {
+        admin.require_auth();
+        let pause_admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if pause_admin != admin {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        let amount = amount.min(Self::remaining_interest_budget(&goal));
+        if amount == 0 {
+            return Ok(goal.total_interest);
+        }
+
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        Self::record_high_water(&mut goal);
+        Self::record_completion(&mut goal, env.ledger().timestamp());
+        goal.total_interest = goal
+            .total_interest
+            .checked_add(amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        goal.updated_at = env.ledger().timestamp();
+        let total_interest = goal.total_interest;
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("interest"), &admin, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("interest")),
+            (goal_id, amount),
+        );
+
+        Ok(total_interest)
+    }
+
+    /// Cap the lifetime interest `credit_interest` may add to a goal (pause admin only;
+    /// 0 removes the cap). Interest already credited is kept even above a lower cap.
+    pub fn set_max_interest(
+        env: Env,
+        admin: Address,
+        goal_id: u32,
+        max_interest: i128,
+    ) -> Result<(), SavingsGoalError> {
+        admin.require_auth();
+        let pause_admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if pause_admin != admin {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if max_interest < 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        goal.max_interest = max_interest;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("int_cap"), &admin, true);
+        Ok(())
+    }
+
+    /// Interest a goal can still receive before hitting `max_interest`; `i128::MAX` when
+    /// uncapped and 0 for a missing goal.
+    pub fn get_remaining_interest_budget(env: Env, goal_id: u32) -> i128 {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => Self::remaining_interest_budget(&goal),
+            None => 0,
+        }
+    }
+
+    fn remaining_interest_budget(goal: &SavingsGoal) -> i128 {
+        if goal.max_interest == 0 {
+            i128::MAX
+        } else {
+            (goal.max_interest - goal.total_interest).max(0)
+        }
+    }
+
+    /// Sum of `total_interest` over all goals owned by `owner`. Saturates at `i128::MAX`
+    /// rather than overflowing.
+    pub fn get_total_interest(env: Env, owner: Address) -> i128 {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut total = 0i128;
+        for (_, goal) in goals.iter() {
+            if goal.owner == owner {
+                total = match total.checked_add(goal.total_interest) {
+                    Some(sum) => sum,
+                    None => return i128::MAX,
+                };
+            }
+        }
+        total
+    }
+
+    /// Sum of `current_amount` over all goals owned by `owner`. Saturates at `i128::MAX`
+    /// rather than overflowing.
+    pub fn get_total_saved(env: Env, owner: Address) -> i128 {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut total = 0i128;
+        for (_, goal) in goals.iter() {
+            if goal.owner == owner {
+                total = match total.checked_add(goal.current_amount) {
+                    Some(sum) => sum,
+                    None => return i128::MAX,
+                };
+            }
+        }
+        total
+    }
+
+    /// Set the display currency and conversion rate for a goal. Stored amounts stay in
+    /// token units; the rate only affects `get_target_in_display`/`get_current_in_display`.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the goal owner)
+    /// * `goal_id` - ID of the goal
+    /// * `display_currency` - Currency code to display, e.g. `PHP`
+    /// * `rate_to_token_bps` - Display units per token unit in basis points (must be positive)
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    /// - If rate_to_token_bps is zero
+    pub fn set_display_currency(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        display_currency: Symbol,
+        rate_to_token_bps: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+
+        if rate_to_token_bps == 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        goal.display_currency = Some(display_currency);
+        goal.rate_to_token_bps = rate_to_token_bps;
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Ok(true)
+    }
+
+    /// Target amount converted with the goal's display rate (0 if the goal doesn't exist)
+    pub fn get_target_in_display(env: Env, goal_id: u32) -> i128 {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => Self::to_display(goal.target_amount, goal.rate_to_token_bps),
+            None => 0,
+        }
+    }
+
+    /// Current amount converted with the goal's display rate (0 if the goal doesn't exist)
+    pub fn get_current_in_display(env: Env, goal_id: u32) -> i128 {
+        match Self::get_goal(env, goal_id) {
+            Some(goal) => Self::to_display(goal.current_amount, goal.rate_to_token_bps),
+            None => 0,
+        }
+    }
+
+    fn to_display(amount: i128, rate_to_token_bps: u32) -> i128 {
+        amount.saturating_mul(rate_to_token_bps as i128) / 10_000
+    }
+
+    /// Get current nonce for an address (for import_snapshot replay protection).
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address))
+            .unwrap_or(0)
+    }
+
+    /// Nonce the next nonce-protected call from `address` must pass. `import_snapshot`,
+    /// `close_account`, `allocate_savings_by_priority` and `create_goals_batch` share this
+    /// single counter, which advances by one after each successful call.
+    pub fn get_next_nonce(env: Env, address: Address) -> u64 {
+        Self::get_nonce(env, address)
+    }
+
+    /// Check a nonce without submitting a call: true only for the current expected value
+    pub fn is_nonce_valid(env: Env, address: Address, nonce: u64) -> bool {
+        Self::get_nonce(env, address) == nonce
+    }
+
+    /// Export all goals as snapshot for backup/migration.
+    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
+        caller.require_auth();
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut list = Vec::new(&env);
+        for i in 1..=next_id {
+            if let Some(g) = goals.get(i) {
+                list.push_back(g);
+            }
+        }
+        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
+        GoalsExportSnapshot {
+            version: SNAPSHOT_VERSION,
+            checksum,
+            next_id,
+            goals: list,
+        }
+    }
+
+    /// Import snapshot (full restore). Validates version and checksum. Requires nonce for replay protection.
+    pub fn import_snapshot(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        snapshot: GoalsExportSnapshot,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(SavingsGoalError::UnsupportedSnapshotVersion);
+        }
+        let expected =
+            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
+        if snapshot.checksum != expected {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(SavingsGoalError::SnapshotChecksumMismatch);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
+        for g in snapshot.goals.iter() {
+            goals.set(g.id, g);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
+
+        Self::increment_nonce(&env, &caller);
+        Self::append_audit(&env, symbol_short!("import"), &caller, true);
+        Ok(true)
+    }
+
+    /// Return recent audit log entries.
+    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
+        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
+        let log = log.unwrap_or_else(|| Vec::new(&env));
+        let len = log.len();
+        let cap = MAX_AUDIT_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        if from_index >= len {
+            return out;
+        }
+        let end = (from_index + cap).min(len);
+        for i in from_index..end {
+            if let Some(entry) = log.get(i) {
+                out.push_back(entry);
+            }
+        }
+        out
+    }
+
+    /// Audit entries made by `caller` only. `from_index` counts within the caller's
+    /// own entries, so pages don't shift when other owners are active.
+    pub fn get_my_audit_log(
+        env: Env,
+        caller: Address,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AuditEntry> {
+        caller.require_auth();
+        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
+        let log = log.unwrap_or_else(|| Vec::new(&env));
+        let cap = MAX_AUDIT_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        let mut skipped = 0u32;
+        for entry in log.iter() {
+            if entry.caller != caller {
+                continue;
+            }
+            if skipped < from_index {
+                skipped += 1;
+                continue;
+            }
+            if out.len() >= cap {
+                break;
+            }
+            out.push_back(entry);
+        }
+        out
+    }
+
+    fn require_nonce(env: &Env, address: &Address, expected: u64) -> Result<(), SavingsGoalError> {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        if expected != current {
+            return Err(SavingsGoalError::InvalidNonce);
+        }
+        Ok(())
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        let next = current.checked_add(1).expect("nonce overflow");
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(env));
+        nonces.set(address.clone(), next);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    }
+
+    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
+        let mut c = version as u64 + next_id as u64;
+        for i in 0..goals.len() {
+            if let Some(g) = goals.get(i) {
+                c = c
+                    .wrapping_add(g.id as u64)
+                    .wrapping_add(g.target_amount as u64)
+                    .wrapping_add(g.current_amount as u64)
+                    .wrapping_add(g.high_water_mark as u64);
+            }
+        }
+        c.wrapping_mul(31)
+    }
+
+    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
+        let timestamp = env.ledger().timestamp();
+        let mut log: Vec<AuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("AUDIT"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_AUDIT_ENTRIES {
+            let mut new_log = Vec::new(env);
+            for i in 1..log.len() {
+                if let Some(entry) = log.get(i) {
+                    new_log.push_back(entry);
+                }
+            }
+            log = new_log;
+        }
+        log.push_back(AuditEntry {
+            operation,
+            caller: caller.clone(),
+            timestamp,
+            success,
+        });
+        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+    }
+
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Extend the TTL of instance storage without modifying goals (callable by anyone)
+    pub fn bump_ttl(env: Env) -> bool {
+        Self::extend_instance_ttl(&env);
+        true
+    }
+
+    /// Set time-lock on a goal
+    pub fn set_time_lock(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        unlock_date: u64,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = match goals.get(goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if unlock_date <= current_time {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            return Err(SavingsGoalError::UnlockDateMustBeInFuture);
+        }
+
+        goal.unlock_date = Some(unlock_date);
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
+        Ok(true)
+    }
+
+    /// Create a schedule for automatic savings deposits
+    pub fn create_savings_schedule(
+        env: Env,
+        owner: Address,
+        goal_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> Result<u32, SavingsGoalError> {
+        owner.require_auth();
+        Self::require_owner_not_frozen(&env, &owner)?;
+
+        if amount <= 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+
+        if goal.owner != owner {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            return Err(SavingsGoalError::NextDueDateMustBeInFuture);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let next_schedule_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_SSCH"))
+            .unwrap_or(0u32)
+            + 1;
+
+        let schedule = SavingsSchedule {
+            id: next_schedule_id,
+            owner: owner.clone(),
+            goal_id,
+            amount,
+            next_due,
+            interval,
+            recurring: interval > 0,
+            active: true,
+            created_at: current_time,
+            last_executed: None,
+            missed_count: 0,
+        };
+
+        schedules.set(next_schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
+            (next_schedule_id, owner),
+        );
+
+        Ok(next_schedule_id)
+    }
+
+    /// Modify a savings schedule
+    pub fn modify_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+
+        if amount <= 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            return Err(SavingsGoalError::NextDueDateMustBeInFuture);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules
+            .get(schedule_id)
+            .ok_or(SavingsGoalError::ScheduleNotFound)?;
+
+        if schedule.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        schedule.amount = amount;
+        schedule.next_due = next_due;
+        schedule.interval = interval;
+        schedule.recurring = interval > 0;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
+            (schedule_id, caller),
+        );
+
+        Ok(true)
+    }
+
+    /// Cancel a savings schedule
+    pub fn cancel_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules
+            .get(schedule_id)
+            .ok_or(SavingsGoalError::ScheduleNotFound)?;
+
+        if schedule.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        schedule.active = false;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
+            (schedule_id, caller),
+        );
+
+        Ok(true)
+    }
+
+    /// Execute due savings schedules (public, callable by anyone - keeper pattern).
+    /// A schedule whose deposit an `atomic_funding` goal rejects stays due and emits
+    /// a `sch_skip` event instead of running.
+    pub fn execute_due_savings_schedules(env: Env) -> Result<Vec<u32>, SavingsGoalError> {
+        Self::extend_instance_ttl(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut executed = Vec::new(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        for (schedule_id, mut schedule) in schedules.iter() {
+            if !schedule.active
+                || schedule.next_due > current_time
+                || Self::is_owner_frozen(&env, &schedule.owner)
+            {
+                continue;
+            }
+
+            if let Some(mut goal) = goals.get(schedule.goal_id) {
+                let deposit = match Self::apply_deposit(&env, &mut goal, schedule.amount) {
+                    Ok(deposit) => deposit,
+                    Err(SavingsGoalError::AtomicFundingIncomplete) => {
+                        // Left due; it runs once the goal can take the deposit
+                        env.events().publish(
+                            (symbol_short!("savings"), symbol_short!("sch_skip")),
+                            (schedule_id, schedule.goal_id, schedule.amount),
+                        );
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let is_completed = deposit.was_completed;
+                goals.set(schedule.goal_id, goal.clone());
+
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
+                );
+
+                if is_completed {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                        (schedule.goal_id, goal.owner.clone()),
+                    );
+                }
+                Self::publish_deposit_flags(&env, &goal, &deposit, &goal.owner);
+            }
+
+            schedule.last_executed = Some(current_time);
+
+            if schedule.recurring && schedule.interval > 0 {
+                let mut missed = 0u32;
+                let mut next = schedule.next_due + schedule.interval;
+                while next <= current_time {
+                    missed += 1;
+                    next += schedule.interval;
+                }
+                schedule.missed_count += missed;
+                schedule.next_due = next;
+
+                if missed > 0 {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
+                        (schedule_id, missed),
+                    );
+                }
+            } else {
+                schedule.active = false;
+            }
 
-        Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
-        for g in snapshot.goals.iter() {
-            goals.set(g.id, g);
+            schedules.set(schedule_id, schedule);
+            executed.push_back(schedule_id);
+
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
+                schedule_id,
+            );
         }
+
         env.storage()
             .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+            .set(&symbol_short!("SAV_SCH"), &schedules);
         env.storage()
             .instance()
-            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
-
-        Self::increment_nonce(&env, &caller);
-        Self::append_audit(&env, symbol_short!("import"), &caller, true);
-        Ok(true)
-    }
-
-    /// Return recent audit log entries.
-    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
-        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
-        let log = log.unwrap_or_else(|| Vec::new(&env));
-        let len = log.len();
-        let cap = MAX_AUDIT_ENTRIES.min(limit);
-        let mut out = Vec::new(&env);
-        if from_index >= len {
-            return out;
-        }
-        let end = (from_index + cap).min(len);
-        for i in from_index..end {
-            if let Some(entry) = log.get(i) {
-                out.push_back(entry);
-            }
-        }
-        out
-    }
+            .set(&symbol_short!("GOALS"), &goals);
 
-    fn require_nonce(env: &Env, address: &Address, expected: u64) -> Result<(), SavingsGoalError> {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        if expected != current {
-            return Err(SavingsGoalError::InvalidNonce);
-        }
-        Ok(())
+        Ok(executed)
     }
 
-    fn increment_nonce(env: &Env, address: &Address) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        let next = current.checked_add(1).expect("nonce overflow");
-        let mut nonces: Map<Address, u64> = env
+    /// Get all savings schedules for an owner
+    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
             .storage()
             .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
-    }
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
 
-    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
-        let mut c = version as u64 + next_id as u64;
-        for i in 0..goals.len() {
-            if let Some(g) = goals.get(i) {
-                c = c
-                    .wrapping_add(g.id as u64)
-                    .wrapping_add(g.target_amount as u64)
-                    .wrapping_add(g.current_amount as u64);
+        let mut result = Vec::new(&env);
+        for (_, schedule) in schedules.iter() {
+            if schedule.owner == owner {
+                result.push_back(schedule);
             }
         }
-        c.wrapping_mul(31)
+        result
     }
 
-    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
+    /// Get a specific savings schedule
+    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
             .storage()
             .instance()
-            .get(&symbol_short!("AUDIT"))
-            .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
-                }
-            }
-            log = new_log;
-        }
-        log.push_back(AuditEntry {
-            operation,
-            caller: caller.clone(),
-            timestamp,
-            success,
-        });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
-    }
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        schedules.get(schedule_id)
     }
 
-    /// Set time-lock on a goal
-    pub fn set_time_lock(
+    /// Set up a recurring token contribution into a goal. The owner must approve this
+    /// contract as spender on `usdc_contract`; each run pulls `amount` with `transfer_from`.
+    /// Replaces any existing contribution schedule for the goal.
+    ///
+    /// # Panics
+    /// - If amount or interval_secs is not positive
+    /// - If goal is not found
+    /// - If caller is not the goal owner
+    pub fn schedule_contribution(
         env: Env,
         caller: Address,
         goal_id: u32,
-        unlock_date: u64,
+        amount: i128,
+        interval_secs: u64,
+        usdc_contract: Address,
     ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::extend_instance_ttl(&env);
-
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
 
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-                return Err(SavingsGoalError::GoalNotFound);
-            }
-        };
+        if amount <= 0 || interval_secs == 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
 
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
         if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
             return Err(SavingsGoalError::Unauthorized);
         }
 
-        let current_time = env.ledger().timestamp();
-        if unlock_date <= current_time {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            return Err(SavingsGoalError::UnlockDateMustBeInFuture);
-        }
+        Self::extend_instance_ttl(&env);
 
-        goal.unlock_date = Some(unlock_date);
-        goals.set(goal_id, goal);
+        let mut contributions: Map<u32, ContributionSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIB"))
+            .unwrap_or_else(|| Map::new(&env));
+        contributions.set(
+            goal_id,
+            ContributionSchedule {
+                goal_id,
+                owner: caller.clone(),
+                amount,
+                interval_secs,
+                next_run: env.ledger().timestamp() + interval_secs,
+                usdc_contract,
+            },
+        );
         env.storage()
             .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+            .set(&symbol_short!("CONTRIB"), &contributions);
 
-        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
+        Self::append_audit(&env, symbol_short!("contrib"), &caller, true);
         Ok(true)
     }
 
-    /// Create a schedule for automatic savings deposits
-    pub fn create_savings_schedule(
-        env: Env,
-        owner: Address,
-        goal_id: u32,
-        amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> Result<u32, SavingsGoalError> {
-        owner.require_auth();
-
-        if amount <= 0 {
-            return Err(SavingsGoalError::AmountMustBePositive);
-        }
-
-        let goals: Map<u32, SavingsGoal> = env
+    pub fn get_contribution_schedule(env: Env, goal_id: u32) -> Option<ContributionSchedule> {
+        let contributions: Map<u32, ContributionSchedule> = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
+            .get(&symbol_short!("CONTRIB"))
             .unwrap_or_else(|| Map::new(&env));
+        contributions.get(goal_id)
+    }
 
-        let goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+    /// Run a goal's contribution schedule if it is due (keeper-callable, no auth).
+    ///
+    /// # Returns
+    /// True if a deposit was made. False if nothing was due, or the run was skipped
+    /// because the owner's balance or allowance was insufficient or an `atomic_funding`
+    /// goal would be left short; a skipped run still advances `next_run` and emits a
+    /// `ctb_skip` event.
+    pub fn process_contributions(env: Env, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
 
-        if goal.owner != owner {
-            return Err(SavingsGoalError::Unauthorized);
-        }
+        let mut contributions: Map<u32, ContributionSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIB"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut schedule = match contributions.get(goal_id) {
+            Some(s) => s,
+            None => return Ok(false),
+        };
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            return Err(SavingsGoalError::NextDueDateMustBeInFuture);
+        let now = env.ledger().timestamp();
+        if now < schedule.next_run || Self::is_owner_frozen(&env, &schedule.owner) {
+            return Ok(false);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
+            .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
 
-        let next_schedule_id = env
-            .storage()
+        schedule.next_run = now + schedule.interval_secs;
+        contributions.set(goal_id, schedule.clone());
+        env.storage()
             .instance()
-            .get(&symbol_short!("NEXT_SSCH"))
-            .unwrap_or(0u32)
-            + 1;
+            .set(&symbol_short!("CONTRIB"), &contributions);
 
-        let schedule = SavingsSchedule {
-            id: next_schedule_id,
-            owner: owner.clone(),
-            goal_id,
-            amount,
-            next_due,
-            interval,
-            recurring: interval > 0,
-            active: true,
-            created_at: current_time,
-            last_executed: None,
-            missed_count: 0,
+        let token = TokenClient::new(&env, &schedule.usdc_contract);
+        let contract = env.current_contract_address();
+        if token.balance(&schedule.owner) < schedule.amount
+            || token.allowance(&schedule.owner, &contract) < schedule.amount
+        {
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("ctb_skip")),
+                (goal_id, schedule.owner, schedule.amount),
+            );
+            return Ok(false);
+        }
+
+        let normalized =
+            Self::normalize_token_amount(&env, &schedule.usdc_contract, schedule.amount)?;
+        let deposit = match Self::apply_deposit(&env, &mut goal, normalized) {
+            Ok(deposit) => deposit,
+            Err(SavingsGoalError::AtomicFundingIncomplete) => {
+                env.events().publish(
+                    (symbol_short!("savings"), symbol_short!("ctb_skip")),
+                    (goal_id, schedule.owner, schedule.amount),
+                );
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
         };
 
-        schedules.set(next_schedule_id, schedule);
+        token.transfer_from(&contract, &schedule.owner, &contract, &schedule.amount);
+
+        Self::credit_token(
+            &mut goal,
+            &schedule.usdc_contract,
+            schedule.amount,
+            normalized,
+        )?;
+        goals.set(goal_id, goal.clone());
         env.storage()
             .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+            .set(&symbol_short!("GOALS"), &goals);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsAdded),
+            (goal_id, schedule.owner.clone(), schedule.amount),
+        );
+        Self::publish_deposit_flags(&env, &goal, &deposit, &schedule.owner);
+        Ok(true)
+    }
+
+    /// Set the conversion rate used to normalize deposits of `token` into a goal's
+    /// `current_amount`, in basis points (10000 = 1:1). Pause admin only.
+    pub fn set_token_rate(
+        env: Env,
+        admin: Address,
+        token: Address,
+        rate_bps: u32,
+    ) -> Result<(), SavingsGoalError> {
+        admin.require_auth();
+        let pause_admin = Self::get_pause_admin(&env).ok_or(SavingsGoalError::Unauthorized)?;
+        if pause_admin != admin {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if rate_bps == 0 {
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
+        let mut rates: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("TOK_RATE"))
+            .unwrap_or_else(|| Map::new(&env));
+        rates.set(token.clone(), rate_bps);
         env.storage()
             .instance()
-            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
-
+            .set(&symbol_short!("TOK_RATE"), &rates);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
-            (next_schedule_id, owner),
+            (symbol_short!("savings"), symbol_short!("tok_rate")),
+            (token, rate_bps),
         );
+        Ok(())
+    }
 
-        Ok(next_schedule_id)
+    /// Conversion rate for `token` in basis points; 10000 (1:1) when unset
+    pub fn get_token_rate(env: Env, token: Address) -> u32 {
+        let rates: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("TOK_RATE"))
+            .unwrap_or_else(|| Map::new(&env));
+        rates.get(token).unwrap_or(10_000)
     }
 
-    /// Modify a savings schedule
-    pub fn modify_savings_schedule(
+    /// Deposit `amount` of `token` into a goal. The tokens are moved into the contract,
+    /// the raw amount is tracked per token, and `current_amount` grows by the amount
+    /// normalized with `get_token_rate`. The deposit is otherwise handled like
+    /// `add_to_goal`.
+    ///
+    /// # Returns
+    /// The goal's updated (normalized) current amount
+    ///
+    /// # Panics
+    /// - If amount is not positive
+    /// - If goal is not found or caller is not the owner
+    /// - If the goal uses `atomic_funding` and the deposit does not complete it
+    pub fn add_to_goal_token(
         env: Env,
         caller: Address,
-        schedule_id: u32,
+        goal_id: u32,
         amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> Result<bool, SavingsGoalError> {
+        token: Address,
+    ) -> Result<i128, SavingsGoalError> {
         caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        Self::require_owner_not_frozen(&env, &caller)?;
 
         if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("add_tok"), &caller, false);
             return Err(SavingsGoalError::AmountMustBePositive);
         }
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            return Err(SavingsGoalError::NextDueDateMustBeInFuture);
-        }
-
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
+            .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
+        let mut goal = match goals.get(goal_id) {
+            Some(g) if g.owner == caller => g,
+            _ => {
+                Self::append_audit(&env, symbol_short!("add_tok"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
 
-        let mut schedule = schedules
-            .get(schedule_id)
-            .ok_or(SavingsGoalError::ScheduleNotFound)?;
-
-        if schedule.owner != caller {
-            return Err(SavingsGoalError::Unauthorized);
-        }
+        let normalized = Self::normalize_token_amount(&env, &token, amount)?;
+        let deposit = Self::apply_deposit(&env, &mut goal, normalized)
+            .inspect_err(|_| Self::append_audit(&env, symbol_short!("add_tok"), &caller, false))?;
+        Self::credit_token(&mut goal, &token, amount, normalized)?;
+        let new_total = deposit.new_total;
+        let just_completed = deposit.was_completed && !deposit.previously_completed;
 
-        schedule.amount = amount;
-        schedule.next_due = next_due;
-        schedule.interval = interval;
-        schedule.recurring = interval > 0;
+        TokenClient::new(&env, &token).transfer(&caller, &env.current_contract_address(), &amount);
 
-        schedules.set(schedule_id, schedule);
+        goals.set(goal_id, goal.clone());
         env.storage()
             .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+            .set(&symbol_short!("GOALS"), &goals);
 
+        Self::append_audit(&env, symbol_short!("add_tok"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
-            (schedule_id, caller),
+            (symbol_short!("savings"), SavingsEvent::FundsAdded),
+            (goal_id, caller.clone(), normalized),
         );
+        if just_completed {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                (goal_id, caller.clone()),
+            );
+        }
+        Self::publish_deposit_flags(&env, &goal, &deposit, &caller);
 
-        Ok(true)
+        Ok(new_total)
     }
 
-    /// Cancel a savings schedule
-    pub fn cancel_savings_schedule(
+    /// Withdraw `amount` of `token` from a goal back to the owner. `current_amount`
+    /// drops by the matching share of what the token's deposits were credited, so a
+    /// later rate change neither strands nor inflates the withdrawal.
+    ///
+    /// # Returns
+    /// The goal's updated (normalized) current amount
+    ///
+    /// # Panics
+    /// - If amount is not positive
+    /// - If goal is not found or caller is not the owner
+    /// - If the goal is locked or time-locked
+    /// - If the goal holds less than `amount` of `token`
+    pub fn withdraw_from_goal_token(
         env: Env,
         caller: Address,
-        schedule_id: u32,
-    ) -> Result<bool, SavingsGoalError> {
+        goal_id: u32,
+        amount: i128,
+        token: Address,
+    ) -> Result<i128, SavingsGoalError> {
         caller.require_auth();
+        Self::withdraw_token_to(
+            &env,
+            &caller,
+            goal_id,
+            amount,
+            &token,
+            &caller,
+            symbol_short!("wd_tok"),
+        )
+    }
 
-        Self::extend_instance_ttl(&env);
-
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// Like `withdraw_from_goal_token`, but sends the tokens to `destination`, which
+    /// must be on the goal's allowlist when one is set (see `set_allowed_destinations`).
+    ///
+    /// # Panics
+    /// - Everything `withdraw_from_goal_token` rejects
+    /// - If the goal has an allowlist that doesn't include `destination`
+    pub fn withdraw_to(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+        token: Address,
+        destination: Address,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::withdraw_token_to(
+            &env,
+            &caller,
+            goal_id,
+            amount,
+            &token,
+            &destination,
+            symbol_short!("wd_to"),
+        )
+    }
 
-        let mut schedule = schedules
-            .get(schedule_id)
-            .ok_or(SavingsGoalError::ScheduleNotFound)?;
+    /// Withdraw `amount` of `usdc_contract` from a token-backed goal and use it to pay
+    /// `bill_id` through the bills contract's `pay_bill_usdc`, in one transaction. The
+    /// bill's amount owed is paid to the bills treasury; any excess stays with the
+    /// caller. If either step fails, both are reverted.
+    ///
+    /// # Returns
+    /// The goal's new balance
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_to_pay_bill(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+        usdc_contract: Address,
+        bills_contract: Address,
+        bill_id: u32,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
 
-        if schedule.owner != caller {
-            return Err(SavingsGoalError::Unauthorized);
+        let bills = BillPaymentsClient::new(&env, &bills_contract);
+        let owed = bills
+            .get_amounts_owed(&caller, &env.ledger().timestamp())
+            .iter()
+            .find(|(id, _)| *id == bill_id)
+            .map(|(_, owed)| owed);
+        let owed = match owed {
+            Some(owed) => owed,
+            None => {
+                Self::append_audit(&env, symbol_short!("wd_bill"), &caller, false);
+                return Err(SavingsGoalError::BillNotPayable);
+            }
+        };
+        if amount < owed {
+            Self::append_audit(&env, symbol_short!("wd_bill"), &caller, false);
+            return Err(SavingsGoalError::InsufficientForBill);
         }
 
-        schedule.active = false;
-
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
-
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
-            (schedule_id, caller),
-        );
-
-        Ok(true)
+        let new_total = Self::withdraw_token_to(
+            &env,
+            &caller,
+            goal_id,
+            amount,
+            &usdc_contract,
+            &caller,
+            symbol_short!("wd_bill"),
+        )?;
+        bills.pay_bill_usdc(&caller, &bill_id, &usdc_contract);
+        Ok(new_total)
     }
 
-    /// Execute due savings schedules (public, callable by anyone - keeper pattern)
-    pub fn execute_due_savings_schedules(env: Env) -> Result<Vec<u32>, SavingsGoalError> {
-        Self::extend_instance_ttl(&env);
+    /// Shared body of the token withdrawals, audited under `op`. Sending to anyone other
+    /// than the owner is subject to the goal's destination allowlist.
+    fn withdraw_token_to(
+        env: &Env,
+        caller: &Address,
+        goal_id: u32,
+        amount: i128,
+        token: &Address,
+        destination: &Address,
+        op: Symbol,
+    ) -> Result<i128, SavingsGoalError> {
+        Self::require_not_paused(env, pause_functions::WITHDRAW)?;
+        Self::require_owner_not_frozen(env, caller)?;
 
-        let current_time = env.ledger().timestamp();
-        let mut executed = Vec::new(&env);
+        if amount <= 0 {
+            Self::append_audit(env, op.clone(), caller, false);
+            return Err(SavingsGoalError::AmountMustBePositive);
+        }
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        Self::extend_instance_ttl(env);
 
         let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        for (schedule_id, mut schedule) in schedules.iter() {
-            if !schedule.active || schedule.next_due > current_time {
-                continue;
+            .unwrap_or_else(|| Map::new(env));
+        let mut goal = match goals.get(goal_id) {
+            Some(g) if g.owner == *caller => g,
+            _ => {
+                Self::append_audit(env, op.clone(), caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
             }
+        };
 
-            if let Some(mut goal) = goals.get(schedule.goal_id) {
-                goal.current_amount = goal
-                    .current_amount
-                    .checked_add(schedule.amount)
-                    .ok_or(SavingsGoalError::ArithmeticError)?;
+        if goal.locked {
+            Self::append_audit(env, op.clone(), caller, false);
+            return Err(SavingsGoalError::GoalLocked);
+        }
+        if goal.withdraw_only_when_complete && goal.current_amount < goal.target_amount {
+            Self::append_audit(env, op.clone(), caller, false);
+            return Err(SavingsGoalError::GoalNotComplete);
+        }
+        if let Some(unlock_date) = goal.unlock_date {
+            if env.ledger().timestamp() < unlock_date {
+                Self::append_audit(env, op.clone(), caller, false);
+                return Err(SavingsGoalError::TimeLocked);
+            }
+        }
+        if destination != caller && !Self::destination_allowed(&goal, destination) {
+            Self::append_audit(env, op.clone(), caller, false);
+            return Err(SavingsGoalError::DestinationNotAllowed);
+        }
 
-                let is_completed = goal.current_amount >= goal.target_amount;
-                goals.set(schedule.goal_id, goal.clone());
+        let token_balance = goal.token_balances.get(token.clone()).unwrap_or(0);
+        if amount > token_balance {
+            Self::append_audit(env, op.clone(), caller, false);
+            return Err(SavingsGoalError::InsufficientBalance);
+        }
 
-                env.events().publish(
-                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
-                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
-                );
+        // Release the withdrawn share of the token's credited basis
+        let basis = goal.token_basis.get(token.clone()).unwrap_or(0);
+        let released = basis
+            .checked_mul(amount)
+            .map(|n| n / token_balance)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        goal.token_balances
+            .set(token.clone(), token_balance - amount);
+        goal.token_basis.set(token.clone(), basis - released);
+        goal.current_amount = goal
+            .current_amount
+            .checked_sub(released)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        goal.updated_at = env.ledger().timestamp();
+        let new_total = goal.current_amount;
 
-                if is_completed {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                        (schedule.goal_id, goal.owner),
-                    );
-                }
-            }
+        TokenClient::new(env, token).transfer(
+            &env.current_contract_address(),
+            destination,
+            &amount,
+        );
 
-            schedule.last_executed = Some(current_time);
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
 
-            if schedule.recurring && schedule.interval > 0 {
-                let mut missed = 0u32;
-                let mut next = schedule.next_due + schedule.interval;
-                while next <= current_time {
-                    missed += 1;
-                    next += schedule.interval;
-                }
-                schedule.missed_count += missed;
-                schedule.next_due = next;
+        Self::append_audit(env, op, caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+            (goal_id, caller.clone(), released),
+        );
 
-                if missed > 0 {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
-                        (schedule_id, missed),
-                    );
-                }
-            } else {
-                schedule.active = false;
-            }
+        Ok(new_total)
+    }
 
-            schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+    /// Restrict where `withdraw_to` and `close_account` may send a goal's funds.
+    /// Pass an empty list to lift the restriction.
+    ///
+    /// # Panics
+    /// - If caller is not the goal owner
+    /// - If goal is not found
+    pub fn set_allowed_destinations(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        destinations: Vec<Address>,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_owner_not_frozen(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
 
-            env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
-                schedule_id,
-            );
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
         }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        goal.allowed_destinations = destinations;
+        goal.updated_at = env.ledger().timestamp();
+        goals.set(goal_id, goal);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
 
-        Ok(executed)
+        Self::append_audit(&env, symbol_short!("dest_set"), &caller, true);
+        Ok(true)
     }
 
-    /// Get all savings schedules for an owner
-    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// Withdrawal allowlist for a goal (empty if unrestricted or the goal is missing)
+    pub fn get_allowed_destinations(env: Env, goal_id: u32) -> Vec<Address> {
+        match Self::get_goal(env.clone(), goal_id) {
+            Some(goal) => goal.allowed_destinations,
+            None => Vec::new(&env),
+        }
+    }
 
-        let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
-                result.push_back(schedule);
+    fn destination_allowed(goal: &SavingsGoal, destination: &Address) -> bool {
+        goal.allowed_destinations.is_empty() || goal.allowed_destinations.contains(destination)
+    }
+
+    /// Raw per-token balances held by a goal (empty if the goal is missing)
+    pub fn get_goal_token_balances(env: Env, goal_id: u32) -> Vec<(Address, i128)> {
+        let mut balances = Vec::new(&env);
+        if let Some(goal) = Self::get_goal(env, goal_id) {
+            for (token, amount) in goal.token_balances.iter() {
+                balances.push_back((token, amount));
             }
         }
-        result
+        balances
     }
 
-    /// Get a specific savings schedule
-    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// Part of `current_amount` credited from token deposits
+    fn token_backed_amount(goal: &SavingsGoal) -> Result<i128, SavingsGoalError> {
+        let mut total = 0i128;
+        for (_, basis) in goal.token_basis.iter() {
+            total = total
+                .checked_add(basis)
+                .ok_or(SavingsGoalError::ArithmeticError)?;
+        }
+        Ok(total)
+    }
 
-        schedules.get(schedule_id)
+    /// Record a deposit of `amount` of `token`, credited as `normalized`
+    fn credit_token(
+        goal: &mut SavingsGoal,
+        token: &Address,
+        amount: i128,
+        normalized: i128,
+    ) -> Result<(), SavingsGoalError> {
+        let balance = goal
+            .token_balances
+            .get(token.clone())
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        let basis = goal
+            .token_basis
+            .get(token.clone())
+            .unwrap_or(0)
+            .checked_add(normalized)
+            .ok_or(SavingsGoalError::ArithmeticError)?;
+        goal.token_balances.set(token.clone(), balance);
+        goal.token_basis.set(token.clone(), basis);
+        Ok(())
+    }
+
+    fn normalize_token_amount(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalError> {
+        let rate = Self::get_token_rate(env.clone(), token.clone()) as i128;
+        amount
+            .checked_mul(rate)
+            .map(|n| n / 10_000)
+            .ok_or(SavingsGoalError::ArithmeticError)
     }
 }
 
@@ -3,6 +3,7 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
+    token::{StellarAssetClient, TokenClient},
     Address, Env, String,
 };
 
@@ -1114,3 +1115,1394 @@ fn test_get_goals_paginated_cursor_not_found() {
     assert!(!response.has_more);
     assert_eq!(response.next_cursor, None);
 }
+
+#[test]
+fn test_close_account_sweeps_all_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let destination = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+
+    let other = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&user, &1500);
+    StellarAssetClient::new(&env, &token.address()).mint(&other, &700);
+    let token_client = TokenClient::new(&env, &token.address());
+
+    let goal1 = client.create_goal(&user, &String::from_str(&env, "Rent"), &2000, &2000000000);
+    let goal2 = client.create_goal(&user, &String::from_str(&env, "Trip"), &2000, &2000000000);
+    client.unlock_goal(&user, &goal1);
+    client.unlock_goal(&user, &goal2);
+    client.add_to_goal_token(&user, &goal1, &1000, &token.address());
+    client.add_to_goal_token(&user, &goal2, &500, &token.address());
+
+    // Another user's tokens sit in the same contract
+    let other_goal =
+        client.create_goal(&other, &String::from_str(&env, "Other"), &2000, &2000000000);
+    client.add_to_goal_token(&other, &other_goal, &700, &token.address());
+
+    // Bookkeeping-only deposits are not backed by tokens and are not swept
+    client.add_to_goal(&user, &goal1, &1_000_000);
+
+    let swept = client.close_account(&user, &0, &destination);
+    assert_eq!(swept, 1500);
+
+    assert!(client.get_goal(&goal1).is_none());
+    assert!(client.get_goal(&goal2).is_none());
+    assert_eq!(token_client.balance(&destination), 1500);
+    assert_eq!(client.get_nonce(&user), 1);
+
+    // The second user's deposit survives and can still be withdrawn
+    assert_eq!(token_client.balance(&contract_id), 700);
+    client.unlock_goal(&other, &other_goal);
+    client.withdraw_from_goal_token(&other, &other_goal, &700, &token.address());
+    assert_eq!(token_client.balance(&other), 700);
+}
+
+#[test]
+fn test_set_withdrawable_allows_partial_withdrawal_while_locked() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &1000, &2000000000);
+    client.add_to_goal(&user, &id, &800);
+
+    client.set_withdrawable(&user, &id, &300);
+
+    // Partial withdrawal within the cap succeeds; the goal stays locked
+    assert_eq!(client.withdraw_from_goal(&user, &id, &200), 600);
+    let goal = client.get_goal(&id).unwrap();
+    assert!(goal.locked);
+    assert_eq!(goal.withdrawable_amount, 100);
+
+    // Anything beyond the remaining cap is rejected
+    let res = client.try_withdraw_from_goal(&user, &id, &150);
+    assert_eq!(res, Err(Ok(SavingsGoalError::GoalLocked)));
+
+    assert_eq!(client.withdraw_from_goal(&user, &id, &100), 500);
+    let res = client.try_withdraw_from_goal(&user, &id, &1);
+    assert_eq!(res, Err(Ok(SavingsGoalError::GoalLocked)));
+}
+
+#[test]
+fn test_set_withdrawable_rejects_amount_above_balance() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &1000, &2000000000);
+    client.add_to_goal(&user, &id, &100);
+
+    let res = client.try_set_withdrawable(&user, &id, &101);
+    assert_eq!(res, Err(Ok(SavingsGoalError::InsufficientBalance)));
+    let res = client.try_set_withdrawable(&other, &id, &50);
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
+}
+
+#[test]
+fn test_get_dormant_goals_reports_only_untouched_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user_a = Address::generate(&env);
+    let user_b = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    set_time(&env, 1_000);
+    let dormant_a = client.create_goal(&user_a, &String::from_str(&env, "A"), &1000, &2000000000);
+    let touched = client.create_goal(&user_a, &String::from_str(&env, "B"), &1000, &2000000000);
+    let dormant_b = client.create_goal(&user_b, &String::from_str(&env, "C"), &1000, &2000000000);
+
+    set_time(&env, 80_000);
+    client.add_to_goal(&user_a, &touched, &100);
+
+    set_time(&env, 100_000);
+    let page = client.get_dormant_goals(&admin, &30_000, &100_000, &None, &Some(1));
+    assert_eq!(page.goals.len(), 1);
+    assert_eq!(page.goals.get(0).unwrap().id, dormant_a);
+    assert!(page.has_more);
+
+    let page = client.get_dormant_goals(&admin, &30_000, &100_000, &page.next_cursor, &Some(1));
+    assert_eq!(page.goals.len(), 1);
+    assert_eq!(page.goals.get(0).unwrap().id, dormant_b);
+    assert!(!page.has_more);
+
+    let res = client.try_get_dormant_goals(&user_a, &30_000, &100_000, &None, &None);
+    assert!(matches!(res, Err(Ok(SavingsGoalError::Unauthorized))));
+}
+
+#[test]
+fn test_bump_ttl_requires_no_auth_and_keeps_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Fund"), &1000, &2000000000);
+    client.add_to_goal(&user, &id, &250);
+
+    assert!(client.bump_ttl());
+    // No authorization was required for the bump
+    assert!(env.auths().is_empty());
+
+    let goal = client.get_goal(&id).unwrap();
+    assert_eq!(goal.current_amount, 250);
+    assert!(goal.locked);
+    assert_eq!(client.get_all_goals(&user).len(), 1);
+}
+
+#[test]
+fn test_remaining_to_target_and_surplus() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let under = client.create_goal(&user, &String::from_str(&env, "Under"), &1000, &2000000000);
+    let over = client.create_goal(&user, &String::from_str(&env, "Over"), &1000, &2000000000);
+    client.add_to_goal(&user, &under, &400);
+    client.add_to_goal(&user, &over, &1250);
+
+    assert_eq!(client.get_remaining_to_target(&under), 600);
+    assert_eq!(client.get_surplus(&under), 0);
+
+    assert_eq!(client.get_remaining_to_target(&over), 0);
+    assert_eq!(client.get_surplus(&over), 250);
+}
+
+#[test]
+fn test_lock_on_complete_enabled_locks_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Car"), &1000, &2000000000);
+    client.unlock_goal(&user, &id);
+    client.set_lock_on_complete(&user, &id, &true);
+
+    client.add_to_goal(&user, &id, &600);
+    assert!(!client.get_goal(&id).unwrap().locked);
+
+    client.add_to_goal(&user, &id, &400);
+    assert!(client.get_goal(&id).unwrap().locked);
+    let res = client.try_withdraw_from_goal(&user, &id, &100);
+    assert_eq!(res, Err(Ok(SavingsGoalError::GoalLocked)));
+}
+
+#[test]
+fn test_lock_on_complete_disabled_stays_unlocked() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Car"), &1000, &2000000000);
+    client.unlock_goal(&user, &id);
+
+    client.add_to_goal(&user, &id, &1000);
+    assert!(!client.get_goal(&id).unwrap().locked);
+    assert_eq!(client.withdraw_from_goal(&user, &id, &100), 900);
+}
+
+#[test]
+fn test_display_currency_conversion() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "House"), &5000, &2000000000);
+    client.add_to_goal(&user, &id, &1200);
+
+    // Without a display rate, values are shown 1:1
+    assert_eq!(client.get_target_in_display(&id), 5000);
+
+    client.set_display_currency(&user, &id, &symbol_short!("PHP"), &20_000);
+    assert_eq!(client.get_target_in_display(&id), 10_000);
+    assert_eq!(client.get_current_in_display(&id), 2_400);
+
+    // Stored token amounts are unchanged
+    let goal = client.get_goal(&id).unwrap();
+    assert_eq!(goal.target_amount, 5000);
+    assert_eq!(goal.current_amount, 1200);
+    assert_eq!(goal.display_currency, Some(symbol_short!("PHP")));
+}
+
+#[test]
+fn test_admin_freeze_owner_blocks_only_that_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let suspect = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    let suspect_goal =
+        client.create_goal(&suspect, &String::from_str(&env, "S"), &1000, &2000000000);
+    let other_goal = client.create_goal(&other, &String::from_str(&env, "O"), &1000, &2000000000);
+
+    client.admin_freeze_owner(&admin, &suspect);
+    assert!(client.is_owner_frozen_public(&suspect));
+
+    let res = client.try_add_to_goal(&suspect, &suspect_goal, &100);
+    assert_eq!(res, Err(Ok(SavingsGoalError::OwnerFrozen)));
+    let res = client.try_unlock_goal(&suspect, &suspect_goal);
+    assert_eq!(res, Err(Ok(SavingsGoalError::OwnerFrozen)));
+    let res = client.try_create_goal(&suspect, &String::from_str(&env, "S2"), &10, &2000000000);
+    assert_eq!(res, Err(Ok(SavingsGoalError::OwnerFrozen)));
+
+    // Other owners are unaffected
+    assert_eq!(client.add_to_goal(&other, &other_goal, &100), 100);
+
+    // Only the admin may freeze
+    let res = client.try_admin_freeze_owner(&other, &suspect);
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
+
+    client.admin_unfreeze_owner(&admin, &suspect);
+    assert_eq!(client.add_to_goal(&suspect, &suspect_goal, &100), 100);
+}
+
+#[test]
+fn test_allocate_savings_by_priority_waterfall() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let low = client.create_goal(&user, &String::from_str(&env, "Low"), &1000, &2000000000);
+    let high = client.create_goal(&user, &String::from_str(&env, "High"), &500, &2000000000);
+    client.set_goal_priority(&user, &high, &10);
+    client.set_goal_priority(&user, &low, &1);
+    client.add_to_goal(&user, &high, &200);
+
+    // High needs 300, low gets the remaining 400
+    let allocations = client.allocate_savings_by_priority(&user, &0, &700);
+    assert_eq!(allocations.len(), 2);
+    assert_eq!(allocations.get(0).unwrap().goal_id, high);
+    assert_eq!(allocations.get(0).unwrap().amount, 300);
+    assert_eq!(allocations.get(1).unwrap().goal_id, low);
+    assert_eq!(allocations.get(1).unwrap().amount, 400);
+    assert_eq!(client.get_goal(&high).unwrap().current_amount, 500);
+    assert_eq!(client.get_goal(&low).unwrap().current_amount, 400);
+
+    // Low needs 600; the 100 residue goes to the highest-priority goal
+    let allocations = client.allocate_savings_by_priority(&user, &1, &700);
+    assert_eq!(allocations.len(), 2);
+    assert_eq!(allocations.get(0).unwrap().goal_id, low);
+    assert_eq!(allocations.get(0).unwrap().amount, 600);
+    assert_eq!(allocations.get(1).unwrap().goal_id, high);
+    assert_eq!(allocations.get(1).unwrap().amount, 100);
+    assert_eq!(client.get_goal(&high).unwrap().current_amount, 600);
+    assert_eq!(client.get_goal(&low).unwrap().current_amount, 1000);
+}
+
+#[test]
+fn test_get_next_nonce_matches_consumed_nonce() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.create_goal(&user, &String::from_str(&env, "Fund"), &1000, &2000000000);
+
+    let next = client.get_next_nonce(&user);
+    assert_eq!(next, 0);
+    client.allocate_savings_by_priority(&user, &next, &100);
+
+    let next = client.get_next_nonce(&user);
+    assert_eq!(next, 1);
+    let stale = client.try_allocate_savings_by_priority(&user, &0, &100);
+    assert!(matches!(stale, Err(Ok(SavingsGoalError::InvalidNonce))));
+    client.allocate_savings_by_priority(&user, &next, &100);
+    assert_eq!(client.get_next_nonce(&user), 2);
+}
+
+#[test]
+fn test_scheduled_contribution_deposits_when_due() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&user, &250);
+    let token_client = TokenClient::new(&env, &token.address());
+    token_client.approve(&user, &contract_id, &1_000, &10_000);
+
+    let id = client.create_goal(&user, &String::from_str(&env, "Auto"), &1000, &2000000000);
+    client.schedule_contribution(&user, &id, &100, &86400, &token.address());
+
+    // Before the interval: no-op
+    set_time(&env, 1_000 + 86399);
+    assert!(!client.process_contributions(&id));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 0);
+
+    // After the interval: deposit and advance
+    set_time(&env, 1_000 + 86400);
+    assert!(client.process_contributions(&id));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 100);
+    assert_eq!(token_client.balance(&user), 150);
+    assert_eq!(token_client.balance(&contract_id), 100);
+    assert!(client
+        .get_goal_token_balances(&id)
+        .contains((token.address(), 100)));
+    assert!(!client.process_contributions(&id));
+
+    set_time(&env, 1_000 + 2 * 86400);
+    assert!(client.process_contributions(&id));
+
+    // Only 50 left: the run is skipped, not failed
+    set_time(&env, 1_000 + 3 * 86400);
+    assert!(!client.process_contributions(&id));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 200);
+    assert_eq!(
+        client.get_contribution_schedule(&id).unwrap().next_run,
+        1_000 + 4 * 86400
+    );
+}
+
+#[test]
+fn test_atomic_funding_rejects_partial_deposit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Laptop"), &1000, &2000000000);
+    client.set_atomic_funding(&user, &id, &true);
+
+    let res = client.try_add_to_goal(&user, &id, &999);
+    assert_eq!(res, Err(Ok(SavingsGoalError::AtomicFundingIncomplete)));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 0);
+}
+
+#[test]
+fn test_atomic_funding_accepts_completing_deposit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Laptop"), &1000, &2000000000);
+    client.set_atomic_funding(&user, &id, &true);
+
+    let events_before = env.events().all().len();
+    assert_eq!(client.add_to_goal(&user, &id, &1200), 1200);
+    assert!(client.is_goal_completed(&id));
+
+    // FundsAdded and GoalCompleted, each as struct and enum events
+    let events_after = env.events().all().len();
+    assert_eq!(events_after - events_before, 4);
+}
+
+#[test]
+fn test_atomic_funding_applies_to_every_deposit_path() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let atomic = client.create_goal(&user, &String::from_str(&env, "Laptop"), &1000, &2000000000);
+    let plain = client.create_goal(&user, &String::from_str(&env, "Rainy"), &500, &2000000000);
+    client.set_atomic_funding(&user, &atomic, &true);
+    client.set_goal_priority(&user, &atomic, &10);
+
+    // Priority allocation skips the atomic goal it can't complete
+    let allocations = client.allocate_savings_by_priority(&user, &0, &300);
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations.get(0).unwrap().goal_id, plain);
+    assert_eq!(client.get_goal(&atomic).unwrap().current_amount, 0);
+
+    // ...including for the residue once the plain goal is full
+    let allocations = client.allocate_savings_by_priority(&user, &1, &300);
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations.get(0).unwrap().amount, 300);
+    assert_eq!(client.get_goal(&plain).unwrap().current_amount, 600);
+    assert_eq!(client.get_goal(&atomic).unwrap().current_amount, 0);
+
+    // A short scheduled deposit stays due
+    let schedule_id = client.create_savings_schedule(&user, &atomic, &400, &2_000, &0);
+    set_time(&env, 2_000);
+    assert_eq!(client.execute_due_savings_schedules().len(), 0);
+    assert_eq!(client.get_goal(&atomic).unwrap().current_amount, 0);
+    assert!(client.get_savings_schedule(&schedule_id).unwrap().active);
+
+    // A short token contribution is skipped before any tokens move
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&user, &400);
+    let token_client = TokenClient::new(&env, &token.address());
+    token_client.approve(&user, &contract_id, &400, &10_000);
+    client.schedule_contribution(&user, &atomic, &400, &86400, &token.address());
+    set_time(&env, 2_000 + 86400);
+    assert!(!client.process_contributions(&atomic));
+    assert_eq!(token_client.balance(&user), 400);
+    assert_eq!(client.get_goal(&atomic).unwrap().current_amount, 0);
+}
+
+#[test]
+fn test_merge_goals_sums_amounts_and_removes_source() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let source = client.create_goal(&user, &String::from_str(&env, "Trip"), &500, &2000000000);
+    let dest = client.create_goal(&user, &String::from_str(&env, "Fund"), &1500, &2000000000);
+    client.add_to_goal(&user, &source, &200);
+    client.add_to_goal(&user, &dest, &300);
+    client.unlock_goal(&user, &source);
+    client.unlock_goal(&user, &dest);
+
+    assert_eq!(client.merge_goals(&user, &source, &dest), 500);
+
+    let merged = client.get_goal(&dest).unwrap();
+    assert_eq!(merged.current_amount, 500);
+    assert_eq!(merged.target_amount, 2000);
+    assert!(client.get_goal(&source).is_none());
+    assert_eq!(client.get_all_goals(&user).len(), 1);
+
+    let result = client.try_merge_goals(&user, &dest, &dest);
+    assert_eq!(result, Err(Ok(SavingsGoalError::CannotMergeIntoSelf)));
+}
+
+#[test]
+fn test_merge_goals_rejects_locked_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let source = client.create_goal(&user, &String::from_str(&env, "Trip"), &500, &2000000000);
+    let dest = client.create_goal(&user, &String::from_str(&env, "Fund"), &1500, &2000000000);
+    // New goals start locked; only the destination is unlocked here
+    client.unlock_goal(&user, &dest);
+
+    let result = client.try_merge_goals(&user, &source, &dest);
+    assert_eq!(result, Err(Ok(SavingsGoalError::GoalLocked)));
+    assert!(client.get_goal(&source).is_some());
+}
+
+#[test]
+fn test_get_total_interest_sums_owner_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    let g1 = client.create_goal(&user, &String::from_str(&env, "House"), &5000, &2000000000);
+    let g2 = client.create_goal(&user, &String::from_str(&env, "Car"), &3000, &2000000000);
+    let g3 = client.create_goal(&other, &String::from_str(&env, "Boat"), &3000, &2000000000);
+    client.add_to_goal(&user, &g1, &1000);
+
+    assert_eq!(client.credit_interest(&admin, &g1, &40), 40);
+    assert_eq!(client.credit_interest(&admin, &g1, &10), 50);
+    assert_eq!(client.credit_interest(&admin, &g2, &25), 25);
+    client.credit_interest(&admin, &g3, &99);
+
+    assert_eq!(client.get_goal(&g1).unwrap().current_amount, 1050);
+    assert_eq!(client.get_total_interest(&user), 75);
+    assert_eq!(client.get_total_interest(&other), 99);
+
+    let result = client.try_credit_interest(&user, &g1, &10);
+    assert_eq!(result, Err(Ok(SavingsGoalError::Unauthorized)));
+}
+
+#[test]
+fn test_get_owners_returns_distinct_addresses() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    client.create_goal(&alice, &String::from_str(&env, "A1"), &1000, &2000000000);
+    client.create_goal(&bob, &String::from_str(&env, "B1"), &1000, &2000000000);
+    client.create_goal(&alice, &String::from_str(&env, "A2"), &1000, &2000000000);
+    client.create_goal(&carol, &String::from_str(&env, "C1"), &1000, &2000000000);
+
+    let owners = client.get_owners(&admin, &0, &None);
+    assert_eq!(owners.len(), 3);
+    assert_eq!(owners.get(0), Some(alice.clone()));
+    assert_eq!(owners.get(1), Some(bob.clone()));
+    assert_eq!(owners.get(2), Some(carol));
+
+    let page = client.get_owners(&admin, &1, &Some(1));
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0), Some(bob));
+
+    let result = client.try_get_owners(&alice, &0, &None);
+    assert_eq!(result, Err(Ok(SavingsGoalError::Unauthorized)));
+}
+
+#[test]
+fn test_stretch_target_event_fires_after_completion() {
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Bike"), &1000, &2000000000);
+
+    let result = client.try_set_stretch_target(&user, &id, &1000);
+    assert_eq!(result, Err(Ok(SavingsGoalError::InvalidStretchTarget)));
+    client.set_stretch_target(&user, &id, &1500);
+
+    let stretch_topic: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("savings"), SavingsEvent::StretchReached).into_val(&env);
+    let completed_topic: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("savings"), SavingsEvent::GoalCompleted).into_val(&env);
+    let emitted = |topic: &soroban_sdk::Vec<soroban_sdk::Val>| {
+        env.events().all().iter().filter(|e| e.1 == *topic).count()
+    };
+
+    // Crossing the main target completes the goal but not the stretch
+    client.add_to_goal(&user, &id, &1100);
+    assert!(client.is_goal_completed(&id));
+    assert!(!client.is_stretch_reached(&id));
+    assert_eq!(emitted(&completed_topic), 1);
+    assert_eq!(emitted(&stretch_topic), 0);
+
+    client.add_to_goal(&user, &id, &400);
+    assert!(client.is_stretch_reached(&id));
+    assert_eq!(emitted(&stretch_topic), 1);
+
+    // Further deposits do not re-emit; events accumulate across calls here
+    client.add_to_goal(&user, &id, &10);
+    assert_eq!(emitted(&stretch_topic), 1);
+}
+
+#[test]
+fn test_create_goals_batch_creates_all_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let first = client.create_goal(&user, &String::from_str(&env, "Existing"), &100, &5000);
+
+    let mut inputs = soroban_sdk::Vec::new(&env);
+    for (name, target) in [("Rent", 1200i128), ("School", 3000), ("Phone", 400)] {
+        inputs.push_back(GoalInput {
+            name: String::from_str(&env, name),
+            target_amount: target,
+            target_date: 100_000,
+            category: symbol_short!("plan"),
+        });
+    }
+
+    let ids = client.create_goals_batch(&user, &0, &inputs);
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ids.get(0), Some(first + 1));
+    assert_eq!(ids.get(2), Some(first + 3));
+    assert_eq!(client.get_all_goals(&user).len(), 4);
+    for id in ids.iter() {
+        let goal = client.get_goal(&id).unwrap();
+        assert_eq!(goal.owner, user);
+        assert_eq!(goal.category, Some(symbol_short!("plan")));
+    }
+    assert_eq!(client.get_nonce(&user), 1);
+
+    // One bad entry rejects the batch and creates nothing
+    inputs.set(
+        1,
+        GoalInput {
+            name: String::from_str(&env, "Past"),
+            target_amount: 500,
+            target_date: 10,
+            category: symbol_short!("plan"),
+        },
+    );
+    let result = client.try_create_goals_batch(&user, &1, &inputs);
+    assert_eq!(result, Err(Ok(SavingsGoalError::TargetDateMustBeInFuture)));
+    assert_eq!(client.get_all_goals(&user).len(), 4);
+}
+
+#[test]
+fn test_peek_next_goal_id_matches_created_id() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+
+    let peeked = client.peek_next_goal_id();
+    assert_eq!(peeked, client.peek_next_goal_id());
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &500, &2000000000);
+    assert_eq!(id, peeked);
+    assert_eq!(client.peek_next_goal_id(), id + 1);
+}
+
+#[test]
+fn test_is_nonce_valid_only_for_current_nonce() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.create_goal(&user, &String::from_str(&env, "Fund"), &1000, &2000000000);
+
+    assert!(client.is_nonce_valid(&user, &0));
+    assert!(!client.is_nonce_valid(&user, &1));
+    client.allocate_savings_by_priority(&user, &0, &100);
+
+    assert!(!client.is_nonce_valid(&user, &0));
+    assert!(client.is_nonce_valid(&user, &1));
+    assert!(!client.is_nonce_valid(&user, &2));
+}
+
+#[test]
+fn test_multi_token_deposits_tracked_per_token() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let eurc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&user, &1_000);
+    StellarAssetClient::new(&env, &eurc.address()).mint(&user, &1_000);
+    // 1 EURC counts as 1.1 goal units
+    client.set_token_rate(&admin, &eurc.address(), &11_000);
+
+    let id = client.create_goal(&user, &String::from_str(&env, "Move"), &5_000, &2000000000);
+    assert_eq!(
+        client.add_to_goal_token(&user, &id, &400, &usdc.address()),
+        400
+    );
+    assert_eq!(
+        client.add_to_goal_token(&user, &id, &200, &eurc.address()),
+        620
+    );
+
+    let balances = client.get_goal_token_balances(&id);
+    assert_eq!(balances.len(), 2);
+    assert!(balances.contains((usdc.address(), 400)));
+    assert!(balances.contains((eurc.address(), 200)));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 620);
+    assert_eq!(
+        TokenClient::new(&env, &eurc.address()).balance(&contract_id),
+        200
+    );
+
+    // Withdrawals name the token and cannot exceed that token's balance
+    client.unlock_goal(&user, &id);
+    let result = client.try_withdraw_from_goal_token(&user, &id, &300, &eurc.address());
+    assert_eq!(result, Err(Ok(SavingsGoalError::InsufficientBalance)));
+    assert_eq!(
+        client.withdraw_from_goal_token(&user, &id, &100, &eurc.address()),
+        510
+    );
+    assert_eq!(TokenClient::new(&env, &eurc.address()).balance(&user), 900);
+    assert!(client
+        .get_goal_token_balances(&id)
+        .contains((usdc.address(), 400)));
+}
+
+#[test]
+fn test_token_and_bookkeeping_balances_cannot_be_withdrawn_twice() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&user, &1_000);
+
+    let id = client.create_goal(&user, &String::from_str(&env, "Home"), &5_000, &2000000000);
+    client.unlock_goal(&user, &id);
+    client.add_to_goal_token(&user, &id, &500, &usdc.address());
+    client.add_to_goal(&user, &id, &100);
+
+    // Only the 100 recorded without tokens can leave through the bookkeeping path
+    let result = client.try_withdraw_from_goal(&user, &id, &200);
+    assert_eq!(result, Err(Ok(SavingsGoalError::InsufficientBalance)));
+    assert_eq!(client.withdraw_from_goal(&user, &id, &100), 500);
+
+    // A rate change after the deposit doesn't strand the tokens
+    client.set_token_rate(&admin, &usdc.address(), &20_000);
+    assert_eq!(
+        client.withdraw_from_goal_token(&user, &id, &200, &usdc.address()),
+        300
+    );
+    assert_eq!(
+        client.withdraw_from_goal_token(&user, &id, &300, &usdc.address()),
+        0
+    );
+    assert_eq!(
+        TokenClient::new(&env, &usdc.address()).balance(&user),
+        1_000
+    );
+    let result = client.try_withdraw_from_goal_token(&user, &id, &1, &usdc.address());
+    assert_eq!(result, Err(Ok(SavingsGoalError::InsufficientBalance)));
+}
+
+#[test]
+fn test_get_goals_completed_between() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let done = client.create_goal(&user, &String::from_str(&env, "Done"), &500, &2000000000);
+    let open = client.create_goal(&user, &String::from_str(&env, "Open"), &500, &2000000000);
+
+    set_time(&env, 5_000);
+    client.add_to_goal(&user, &done, &300);
+    client.add_to_goal(&user, &open, &100);
+    assert_eq!(client.get_goal(&done).unwrap().completed_at, None);
+
+    set_time(&env, 10_000);
+    client.add_to_goal(&user, &done, &200);
+    assert_eq!(client.get_goal(&done).unwrap().completed_at, Some(10_000));
+
+    // Later deposits keep the original completion time
+    set_time(&env, 20_000);
+    client.add_to_goal(&user, &done, &50);
+    assert_eq!(client.get_goal(&done).unwrap().completed_at, Some(10_000));
+
+    let hits = client.get_goals_completed_between(&user, &9_000, &10_000);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits.get(0).unwrap().id, done);
+    assert_eq!(
+        client
+            .get_goals_completed_between(&user, &10_001, &30_000)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_get_next_action_follows_goal_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let deadline = 1_000 + 60 * 86400;
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &1000, &deadline);
+
+    assert_eq!(client.get_next_action(&99, &1_000), symbol_short!("NONE"));
+    assert_eq!(
+        client.get_next_action(&id, &1_000),
+        symbol_short!("ON_TRACK")
+    );
+    // Within 30 days of the deadline
+    assert_eq!(
+        client.get_next_action(&id, &(deadline - 10 * 86400)),
+        symbol_short!("FUND")
+    );
+
+    client.add_to_goal(&user, &id, &1000);
+    assert_eq!(client.get_next_action(&id, &1_000), symbol_short!("LOCKED"));
+
+    client.unlock_goal(&user, &id);
+    assert_eq!(
+        client.get_next_action(&id, &1_000),
+        symbol_short!("WITHDRAW")
+    );
+
+    client.withdraw_from_goal(&user, &id, &400);
+    assert_eq!(
+        client.get_next_action(&id, &1_000),
+        symbol_short!("WITHDRAW")
+    );
+
+    client.withdraw_from_goal(&user, &id, &600);
+    assert_eq!(client.get_next_action(&id, &1_000), symbol_short!("DONE"));
+}
+
+#[test]
+fn test_withdraw_only_when_complete() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Commit"), &1000, &2000000000);
+    client.unlock_goal(&user, &id);
+    client.set_withdraw_only_when_complete(&user, &id, &true);
+    client.add_to_goal(&user, &id, &600);
+
+    let result = client.try_withdraw_from_goal(&user, &id, &100);
+    assert_eq!(result, Err(Ok(SavingsGoalError::GoalNotComplete)));
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 600);
+
+    client.add_to_goal(&user, &id, &400);
+    assert_eq!(client.withdraw_from_goal(&user, &id, &100), 900);
+}
+
+#[test]
+fn test_estimate_completion_projects_from_deposit_rate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Laptop"), &1000, &2000000000);
+
+    // No deposits yet: rate is zero
+    assert_eq!(client.estimate_completion(&id, &1_000), u64::MAX);
+
+    // 100 per day for three days
+    for day in 1..=3u64 {
+        set_time(&env, 1_000 + day * 86_400);
+        client.add_to_goal(&user, &id, &100);
+    }
+    let now = 1_000 + 3 * 86_400;
+    // 300 saved in 3 days, 700 left: 7 more days at the same pace
+    assert_eq!(client.estimate_completion(&id, &now), now + 7 * 86_400);
+
+    client.add_to_goal(&user, &id, &700);
+    assert_eq!(client.estimate_completion(&id, &now), 0);
+    assert_eq!(client.estimate_completion(&99, &now), u64::MAX);
+}
+
+#[test]
+fn test_get_lock_status_reports_reason() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    set_time(&env, 1_000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &1000, &2000000000);
+    client.add_to_goal(&user, &id, &500);
+
+    // New goals start manually locked
+    assert_eq!(
+        client.get_lock_status(&id, &1_000),
+        Symbol::new(&env, "MANUAL_LOCK")
+    );
+
+    client.unlock_goal(&user, &id);
+    assert_eq!(
+        client.get_lock_status(&id, &1_000),
+        symbol_short!("UNLOCKED")
+    );
+
+    client.set_time_lock(&user, &id, &5_000);
+    assert_eq!(
+        client.get_lock_status(&id, &1_000),
+        symbol_short!("TIME_LOCK")
+    );
+    assert_eq!(
+        client.get_lock_status(&id, &5_000),
+        symbol_short!("UNLOCKED")
+    );
+
+    client.set_withdraw_only_when_complete(&user, &id, &true);
+    assert_eq!(
+        client.get_lock_status(&id, &5_000),
+        Symbol::new(&env, "INCOMPLETE")
+    );
+    client.add_to_goal(&user, &id, &500);
+    assert_eq!(
+        client.get_lock_status(&id, &5_000),
+        symbol_short!("UNLOCKED")
+    );
+
+    client.admin_freeze_owner(&admin, &user);
+    assert_eq!(client.get_lock_status(&id, &5_000), symbol_short!("FROZEN"));
+
+    assert_eq!(client.get_lock_status(&99, &5_000), symbol_short!("NONE"));
+}
+
+#[test]
+fn test_get_total_saved_sums_owner_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    assert_eq!(client.get_total_saved(&user), 0);
+
+    let a = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&user, &String::from_str(&env, "B"), &1000, &2000000000);
+    let c = client.create_goal(&other, &String::from_str(&env, "C"), &1000, &2000000000);
+    client.add_to_goal(&user, &a, &300);
+    client.add_to_goal(&user, &b, &450);
+    client.add_to_goal(&other, &c, &999);
+
+    assert_eq!(client.get_total_saved(&user), 750);
+}
+
+#[test]
+fn test_withdraw_to_respects_destination_allowlist() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&user, &1_000);
+    let token = TokenClient::new(&env, &usdc.address());
+
+    let id = client.create_goal(&user, &String::from_str(&env, "Safe"), &5_000, &2000000000);
+    client.add_to_goal_token(&user, &id, &600, &usdc.address());
+    client.unlock_goal(&user, &id);
+
+    let mut allowed = Vec::new(&env);
+    allowed.push_back(bank.clone());
+    client.set_allowed_destinations(&user, &id, &allowed);
+    assert_eq!(client.get_allowed_destinations(&id), allowed);
+
+    assert_eq!(
+        client.withdraw_to(&user, &id, &200, &usdc.address(), &bank),
+        400
+    );
+    assert_eq!(token.balance(&bank), 200);
+
+    let result = client.try_withdraw_to(&user, &id, &200, &usdc.address(), &attacker);
+    assert_eq!(result, Err(Ok(SavingsGoalError::DestinationNotAllowed)));
+    assert_eq!(token.balance(&attacker), 0);
+    let result = client.try_close_account(&user, &0, &attacker);
+    assert_eq!(result, Err(Ok(SavingsGoalError::DestinationNotAllowed)));
+
+    // Lifting the allowlist lets funds go anywhere again
+    client.set_allowed_destinations(&user, &id, &Vec::new(&env));
+    client.withdraw_to(&user, &id, &100, &usdc.address(), &attacker);
+    assert_eq!(token.balance(&attacker), 100);
+}
+
+#[test]
+fn test_get_completion_stats() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    let a = client.create_goal(&alice, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&alice, &String::from_str(&env, "B"), &2000, &2000000000);
+    let c = client.create_goal(&bob, &String::from_str(&env, "C"), &500, &2000000000);
+    client.create_goal(&bob, &String::from_str(&env, "D"), &1500, &2000000000);
+    client.add_to_goal(&alice, &a, &1000);
+    client.add_to_goal(&alice, &b, &400);
+    client.add_to_goal(&bob, &c, &600);
+
+    let result = client.try_get_completion_stats(&alice);
+    assert_eq!(result, Err(Ok(SavingsGoalError::Unauthorized)));
+
+    let stats = client.get_completion_stats(&admin);
+    assert_eq!(stats.total_goals, 4);
+    assert_eq!(stats.completed_goals, 2);
+    assert_eq!(stats.total_target, 5000);
+    assert_eq!(stats.total_saved, 2000);
+    assert_eq!(stats.completion_rate_bps, 5000);
+}
+
+#[test]
+fn test_get_my_audit_log_filters_by_caller() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&alice, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&bob, &String::from_str(&env, "B"), &1000, &2000000000);
+    client.add_to_goal(&alice, &a, &100);
+    client.add_to_goal(&bob, &b, &200);
+    client.add_to_goal(&alice, &a, &300);
+
+    let global = client.get_audit_log(&0, &100);
+    let alice_log = client.get_my_audit_log(&alice, &0, &100);
+    let bob_log = client.get_my_audit_log(&bob, &0, &100);
+    assert!(!alice_log.is_empty() && !bob_log.is_empty());
+    assert_eq!(alice_log.len() + bob_log.len(), global.len());
+    assert!(alice_log.iter().all(|entry| entry.caller == alice));
+    assert!(bob_log.iter().all(|entry| entry.caller == bob));
+
+    // Paging counts only the caller's own entries
+    let page = client.get_my_audit_log(&alice, &1, &1);
+    assert_eq!(page.len(), 1);
+    let expected = alice_log.get(1).unwrap();
+    let entry = page.get(0).unwrap();
+    assert_eq!(entry.operation, expected.operation);
+    assert_eq!(entry.caller, alice);
+}
+
+#[test]
+fn test_create_goal_from_template() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    set_time(&env, 1_000);
+
+    let key = symbol_short!("EMERG");
+    let name = String::from_str(&env, "Emergency Fund");
+    let result = client.try_register_template(&user, &key, &name, &3_000, &(90 * 86400));
+    assert_eq!(result, Err(Ok(SavingsGoalError::Unauthorized)));
+    client.register_template(&admin, &key, &name, &3_000, &(90 * 86400));
+    assert_eq!(client.get_templates().len(), 1);
+
+    let result = client.try_create_from_template(&user, &0, &symbol_short!("NOPE"));
+    assert_eq!(result, Err(Ok(SavingsGoalError::TemplateNotFound)));
+
+    let id = client.create_from_template(&user, &0, &key);
+    let goal = client.get_goal(&id).unwrap();
+    assert_eq!(goal.owner, user);
+    assert_eq!(goal.name, name);
+    assert_eq!(goal.target_amount, 3_000);
+    assert_eq!(goal.target_date, 1_000 + 90 * 86400);
+    assert_eq!(goal.category, Some(key));
+    assert_eq!(client.get_nonce(&user), 1);
+}
+
+#[test]
+fn test_high_water_mark_survives_withdrawals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Peak"), &5000, &2000000000);
+    client.add_to_goal(&user, &id, &1500);
+    client.add_to_goal(&user, &id, &1000);
+    assert_eq!(client.get_goal(&id).unwrap().high_water_mark, 2500);
+
+    client.unlock_goal(&user, &id);
+    client.withdraw_from_goal(&user, &id, &2000);
+    client.add_to_goal(&user, &id, &700);
+
+    let goal = client.get_goal(&id).unwrap();
+    assert_eq!(goal.current_amount, 1200);
+    assert_eq!(goal.high_water_mark, 2500);
+}
+
+#[test]
+fn test_interest_stops_at_max_interest_cap() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    let id = client.create_goal(&user, &String::from_str(&env, "Promo"), &5000, &2000000000);
+    client.add_to_goal(&user, &id, &1000);
+    assert_eq!(client.get_remaining_interest_budget(&id), i128::MAX);
+
+    client.set_max_interest(&admin, &id, &100);
+    assert_eq!(client.credit_interest(&admin, &id, &60), 60);
+    assert_eq!(client.get_remaining_interest_budget(&id), 40);
+
+    // Only the remaining 40 is credited, then nothing more
+    assert_eq!(client.credit_interest(&admin, &id, &60), 100);
+    assert_eq!(client.credit_interest(&admin, &id, &60), 100);
+    assert_eq!(client.get_remaining_interest_budget(&id), 0);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 1100);
+
+    // Principal and credited interest stay withdrawable
+    client.unlock_goal(&user, &id);
+    assert_eq!(client.withdraw_from_goal(&user, &id, &1100), 0);
+}
+
+#[test]
+fn test_consistency_scores_even_deposits_above_bursty_ones() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 500);
+    let even = client.create_goal(&user, &String::from_str(&env, "Even"), &50000, &2000000000);
+    let bursty = client.create_goal(
+        &user,
+        &String::from_str(&env, "Bursty"),
+        &50000,
+        &2000000000,
+    );
+
+    for ts in [1000, 2000, 3000, 4000] {
+        set_time(&env, ts);
+        client.add_to_goal(&user, &even, &100);
+    }
+    for ts in [1000, 1010, 1020, 4000] {
+        set_time(&env, ts);
+        client.add_to_goal(&user, &bursty, &100);
+    }
+    assert_eq!(client.get_contribution_history(&bursty).len(), 4);
+
+    let even_score = client.get_consistency(&even, &4000);
+    let bursty_score = client.get_consistency(&bursty, &4000);
+    assert_eq!(even_score, 100);
+    assert!(bursty_score < even_score);
+
+    // A long stall since the last deposit lowers an otherwise perfect score
+    assert!(client.get_consistency(&even, &10000) < even_score);
+
+    // Too little history to judge
+    let fresh = client.create_goal(&user, &String::from_str(&env, "Fresh"), &50000, &2000000000);
+    client.add_to_goal(&user, &fresh, &100);
+    assert_eq!(client.get_consistency(&fresh, &4000), 0);
+}
+
+#[test]
+fn test_auto_roll_advances_target_on_completion() {
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Monthly"), &100, &2000000000);
+
+    let result = client.try_set_auto_roll(&user, &id, &true, &0);
+    assert_eq!(result, Err(Ok(SavingsGoalError::InvalidRollIncrement)));
+    client.set_auto_roll(&user, &id, &true, &100);
+
+    client.add_to_goal(&user, &id, &130);
+    let rolled_topic: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("savings"), SavingsEvent::GoalRolled).into_val(&env);
+    assert!(env.events().all().iter().any(|e| e.1 == rolled_topic));
+
+    let goal = client.get_goal(&id).unwrap();
+    assert_eq!(goal.target_amount, 200);
+    assert_eq!(goal.current_amount, 130);
+    assert_eq!(goal.completed_at, None);
+    assert!(!client.is_goal_completed(&id));
+    assert_eq!(client.get_roll_count(&id), 1);
+
+    // A deposit clearing several increments rolls past all of them at once
+    client.add_to_goal(&user, &id, &250);
+    assert_eq!(client.get_goal(&id).unwrap().target_amount, 400);
+    assert_eq!(client.get_roll_count(&id), 3);
+}
+
+#[test]
+fn test_auto_roll_applies_to_every_deposit_path() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Monthly"), &100, &2000000000);
+    client.set_auto_roll(&user, &id, &true, &100);
+
+    // Priority allocation fills the goal to its target, which rolls it forward
+    client.allocate_savings_by_priority(&user, &0, &100);
+    assert_eq!(client.get_goal(&id).unwrap().target_amount, 200);
+    assert_eq!(client.get_roll_count(&id), 1);
+
+    // Scheduled deposit
+    client.create_savings_schedule(&user, &id, &100, &2_000, &0);
+    set_time(&env, 2_000);
+    client.execute_due_savings_schedules();
+    assert_eq!(client.get_goal(&id).unwrap().target_amount, 300);
+    assert_eq!(client.get_roll_count(&id), 2);
+
+    // Recurring token contribution
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&user, &100);
+    TokenClient::new(&env, &token.address()).approve(&user, &contract_id, &100, &10_000);
+    client.schedule_contribution(&user, &id, &100, &86400, &token.address());
+    set_time(&env, 2_000 + 86400);
+    assert!(client.process_contributions(&id));
+    assert_eq!(client.get_goal(&id).unwrap().target_amount, 400);
+    assert_eq!(client.get_roll_count(&id), 3);
+    assert!(!client.is_goal_completed(&id));
+}
+
+#[test]
+fn test_token_deposits_share_add_to_goal_handling() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&user, &1_000);
+    let token_client = TokenClient::new(&env, &usdc.address());
+
+    let atomic = client.create_goal(&user, &String::from_str(&env, "Laptop"), &500, &2000000000);
+    client.set_atomic_funding(&user, &atomic, &true);
+    let result = client.try_add_to_goal_token(&user, &atomic, &400, &usdc.address());
+    assert_eq!(result, Err(Ok(SavingsGoalError::AtomicFundingIncomplete)));
+    assert_eq!(token_client.balance(&user), 1_000);
+
+    let id = client.create_goal(&user, &String::from_str(&env, "Monthly"), &100, &2000000000);
+    client.set_auto_roll(&user, &id, &true, &100);
+    client.add_to_goal_token(&user, &id, &150, &usdc.address());
+    assert_eq!(client.get_goal(&id).unwrap().target_amount, 200);
+    assert_eq!(client.get_roll_count(&id), 1);
+
+    let locking = client.create_goal(&user, &String::from_str(&env, "Vault"), &100, &2000000000);
+    client.unlock_goal(&user, &locking);
+    client.set_lock_on_complete(&user, &locking, &true);
+    client.add_to_goal_token(&user, &locking, &100, &usdc.address());
+    assert!(client.get_goal(&locking).unwrap().locked);
+}
+
+#[test]
+fn test_close_account_skips_held_goals_and_purges_goal_keys() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let destination = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&user, &1_000);
+    TokenClient::new(&env, &token.address()).approve(&user, &contract_id, &1_000, &10_000);
+
+    let held = client.create_goal(&user, &String::from_str(&env, "House"), &5_000, &2000000000);
+    client.unlock_goal(&user, &held);
+    client.set_withdraw_only_when_complete(&user, &held, &true);
+    client.add_to_goal_token(&user, &held, &300, &token.address());
+
+    let closing = client.create_goal(&user, &String::from_str(&env, "Trip"), &2_000, &2000000000);
+    client.unlock_goal(&user, &closing);
+    client.add_to_goal_token(&user, &closing, &200, &token.address());
+    client.schedule_contribution(&user, &closing, &100, &86400, &token.address());
+    let schedule_id = client.create_savings_schedule(&user, &closing, &100, &5_000, &0);
+
+    assert_eq!(client.close_account(&user, &0, &destination), 200);
+    assert!(client.get_goal(&held).is_some());
+    assert!(client.get_goal(&closing).is_none());
+
+    // Nothing keyed by the closed goal is left behind
+    assert!(client.get_contribution_schedule(&closing).is_none());
+    assert!(client.get_savings_schedule(&schedule_id).is_none());
+    assert_eq!(client.get_contribution_history(&closing).len(), 0);
+    set_time(&env, 1_000 + 86400);
+    assert!(!client.process_contributions(&closing));
+    assert_eq!(client.execute_due_savings_schedules().len(), 0);
+}
@@ -0,0 +1,70 @@
+use bill_payments::{BillPayments, BillPaymentsClient};
+use savings_goals::{SavingsGoalContract, SavingsGoalContractClient, SavingsGoalError};
+use soroban_sdk::testutils::Address as AddressTrait;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Env, String};
+
+#[test]
+fn withdraw_to_pay_bill_moves_savings_and_marks_bill_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let savings_id = env.register_contract(None, SavingsGoalContract);
+    let savings = SavingsGoalContractClient::new(&env, &savings_id);
+    let bills_id = env.register_contract(None, BillPayments);
+    let bills = BillPaymentsClient::new(&env, &bills_id);
+
+    let user = <Address as AddressTrait>::generate(&env);
+    let admin = <Address as AddressTrait>::generate(&env);
+    let treasury = <Address as AddressTrait>::generate(&env);
+    let token_admin = <Address as AddressTrait>::generate(&env);
+    let usdc = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token = TokenClient::new(&env, &usdc);
+    StellarAssetClient::new(&env, &usdc).mint(&user, &5_000);
+
+    savings.init();
+    bills.set_pause_admin(&admin, &admin);
+    bills.set_treasury(&admin, &treasury);
+
+    let goal_id = savings.create_goal(
+        &user,
+        &String::from_str(&env, "Rainy day"),
+        &10_000,
+        &2_000_000_000,
+    );
+    savings.add_to_goal_token(&user, &goal_id, &4_000, &usdc);
+    savings.unlock_goal(&user, &goal_id);
+    let bill_id = bills.create_bill(
+        &user,
+        &String::from_str(&env, "Power"),
+        &1_500,
+        &2_000_000_000,
+        &false,
+        &0,
+        &0,
+    );
+
+    // Too small a withdrawal leaves both contracts untouched
+    assert_eq!(
+        savings.try_withdraw_to_pay_bill(&user, &goal_id, &1_000, &usdc, &bills_id, &bill_id),
+        Err(Ok(SavingsGoalError::InsufficientForBill))
+    );
+    assert!(!bills.get_bill(&bill_id).unwrap().paid);
+
+    let new_total =
+        savings.withdraw_to_pay_bill(&user, &goal_id, &1_500, &usdc, &bills_id, &bill_id);
+    assert_eq!(new_total, 2_500);
+    assert_eq!(savings.get_goal(&goal_id).unwrap().current_amount, 2_500);
+    assert!(bills.get_bill(&bill_id).unwrap().paid);
+    assert_eq!(token.balance(&treasury), 1_500);
+    assert_eq!(token.balance(&savings_id), 2_500);
+    assert_eq!(token.balance(&user), 1_000);
+
+    // The bill can't be paid twice
+    assert_eq!(
+        savings.try_withdraw_to_pay_bill(&user, &goal_id, &1_500, &usdc, &bills_id, &bill_id),
+        Err(Ok(SavingsGoalError::BillNotPayable))
+    );
+}